@@ -168,17 +168,37 @@ extern "C" {
   pub fn COPT_ClearProb(prob: *mut copt_prob) -> c_int;
   pub fn COPT_DeleteProb(probP: *mut *mut copt_prob) -> c_int;
 
-  pub fn COPT_LoadProb(prob: copt_prob, nCol: c_int, nRow: c_int, iObjSense: c_int, dObjConst: c_double, colObj: *const c_double, colMatBeg: *const c_int, colMatCnt: *const c_int, colMatIdx: *const c_int, colMatElem: *const c_double, colType: *const c_char, colLower: *const c_double, colUpper: *const c_double, rowSense: *const c_char, rowBound: *const c_double, rowUpper: *const c_double, colNames: *const *const c_char, rowNames: *const *const c_char) -> c_int;
+  pub fn COPT_LoadProb(prob: *mut copt_prob, nCol: c_int, nRow: c_int, iObjSense: c_int, dObjConst: c_double, colObj: *const c_double, colMatBeg: *const c_int, colMatCnt: *const c_int, colMatIdx: *const c_int, colMatElem: *const c_double, colType: *const c_char, colLower: *const c_double, colUpper: *const c_double, rowSense: *const c_char, rowBound: *const c_double, rowUpper: *const c_double, colNames: *const *const c_char, rowNames: *const *const c_char) -> c_int;
   pub fn COPT_AddCol(prob: *mut copt_prob, dColObj:c_double,nColMatCnt: c_int, colMatIdx: *const c_int, colMatElem: *const c_double, cColType: c_char, dColLower: c_double, dColUpper: c_double, colName: *const c_char) -> c_int;
   pub fn COPT_AddPSDCol(prob: *mut copt_prob, colDim: c_int, name: *const c_char) -> c_int;
   pub fn COPT_AddRow(prob: *mut copt_prob, nRowMatCnt: c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, cRowSense: c_char, dRowBound: c_double, dRowUpper: c_double, rowName : *const c_char) -> c_int;
-  pub fn COPT_AddCols(prob: *mut copt_prob, nAddCol: c_int, colObj: *const c_double, colMatBeg: *const c_int, colMatCnt: *const c_int, colMatIdx: *const c_int, colMatElem: *const c_double, colType: *const c_char, colLower: *const c_double, colUpper: *const c_double, rowName : *const *const c_char) -> c_int;
+  pub fn COPT_AddPSDConstr(prob: *mut copt_prob, nRowMatCnt: c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, nColCnt: c_int, psdColIdx: *const c_int, symMatIdx: *const c_int, cRowSense: c_char, dRowBound: c_double, dRowUpper: c_double, name: *const c_char) -> c_int;
+  pub fn COPT_AddLMIConstr(prob: *mut copt_prob, nDim: c_int, nLMIMatCnt: c_int, colIdx: *const c_int, symMatIdx: *const c_int, constMatIdx: c_int, name: *const c_char) -> c_int;
+  pub fn COPT_AddLazyConstr(prob: *mut copt_prob, nRowMatCnt: c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, cRowSense: c_char, dRowBound: c_double, dRowUpper: c_double, name: *const c_char) -> c_int;
+  pub fn COPT_AddSymMat(prob: *mut copt_prob, ndim: c_int, nelem: c_int, rows: *const c_int, cols: *const c_int, elems: *const c_double) -> c_int;
+  pub fn COPT_AddSOSs(prob: *mut copt_prob, nAddSOS: c_int, sosType: *const c_int, sosMatBeg: *const c_int, sosMatCnt: *const c_int, sosMatIdx: *const c_int, sosMatWt: *const c_double) -> c_int;
+  pub fn COPT_AddIndicator(prob: *mut copt_prob, binColIdx: c_int, binColVal: c_int, nRowMatCnt: c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, cRowSense: c_char, dRowBound: c_double) -> c_int;
+  pub fn COPT_AddCones(prob: *mut copt_prob, nAddCone: c_int, coneType: *const c_int, coneBeg: *const c_int, coneCnt: *const c_int, coneIdx: *const c_int) -> c_int;
+  pub fn COPT_AddExpCones(prob: *mut copt_prob, nAddCone: c_int, coneType: *const c_int, coneIdx: *const c_int) -> c_int;
+  pub fn COPT_AddQConstr(prob: *mut copt_prob, nRowMatCnt: c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, nQMatCnt: c_int, qMatRow: *const c_int, qMatCol: *const c_int, qMatElem: *const c_double, cRowSense: c_char, dRowBound: c_double, name: *const c_char) -> c_int;
+  pub fn COPT_AddCols(prob: *mut copt_prob, nAddCol: c_int, colObj: *const c_double, colMatBeg: *const c_int, colMatCnt: *const c_int, colMatIdx: *const c_int, colMatElem: *const c_double, colType: *const c_char, colLower: *const c_double, colUpper: *const c_double, colNames: *const *const c_char) -> c_int;
   pub fn COPT_AddPSDCols(prob: *mut copt_prob, nAddCol: c_int, colDims: *const c_int, names : *const *const c_char) -> c_int;
-  pub fn COPT_AddRows(prob: *mut copt_prob, nAddRow: c_int, rowMatBeg: *const c_int, rowMatCnt: *const c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, rowSense : c_char, rowBound: c_double, rowUpper: c_double, rowNames : *const *const c_char) -> c_int;
+  pub fn COPT_AddRows(prob: *mut copt_prob, nAddRow: c_int, rowMatBeg: *const c_int, rowMatCnt: *const c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, rowSense: *const c_char, rowBound: *const c_double, rowUpper: *const c_double, rowNames : *const *const c_char) -> c_int;
   // ...
   pub fn COPT_GetCols(prob: *mut copt_prob, nCol: c_int, list: *const c_int, colMatBeg: *mut c_int, colMatCnt: *mut c_int, colMatIdx: *mut c_int, colMatElem: *mut c_double, nElemSize: c_int, pReqSize: *mut c_int) -> c_int;
-  pub fn COPT_GetPSDCols(prob: *mut copt_prob, nCol: c_int, list: *mut c_int, colDims: *mut c_int, colLens: *mut c_int) -> c_int;
+  pub fn COPT_GetPSDCols(prob: *mut copt_prob, nCol: c_int, list: *const c_int, colDims: *mut c_int, colLens: *mut c_int) -> c_int;
+  pub fn COPT_GetLMIConstr(prob: *mut copt_prob, nLMI: c_int, list: *const c_int, constMatIdx: *mut c_int, nDim: *mut c_int, nLen: *mut c_int, colBeg: *mut c_int, colCnt: *mut c_int, colIdx: *mut c_int, symMatIdx: *mut c_int, nElemSize: c_int, pReqSize: *mut c_int) -> c_int;
   pub fn COPT_GetRows(prob: *mut copt_prob, nRow: c_int, list: *const c_int, rowMatBeg: *mut c_int, rowMatCnt: *mut c_int, rowMatIdx: *mut c_int, rowMatElem: *mut c_double, nElemSize: c_int, pReqSize: *mut c_int) -> c_int;
+  pub fn COPT_GetQConstr(prob: *mut copt_prob, qConstrIdx: c_int, qMatRow: *mut c_int, qMatCol: *mut c_int, qMatElem: *mut c_double, nQElemSize: c_int, pQReqSize: *mut c_int, rowMatIdx: *mut c_int, rowMatElem: *mut c_double, cRowSense: *mut c_char, dRowBound: *mut c_double, nRowElemSize: c_int, pRowReqSize: *mut c_int) -> c_int;
+  // ...
+  pub fn COPT_GetColInfo(prob: *mut copt_prob, infoName: *const c_char, num: c_int, list: *const c_int, info: *mut c_double) -> c_int;
+  pub fn COPT_GetRowInfo(prob: *mut copt_prob, infoName: *const c_char, num: c_int, list: *const c_int, info: *mut c_double) -> c_int;
+  pub fn COPT_GetQConstrInfo(prob: *mut copt_prob, infoName: *const c_char, num: c_int, list: *const c_int, info: *mut c_double) -> c_int;
+  // ...
+  pub fn COPT_GetColIdx(prob: *mut copt_prob, colName: *const c_char, p_iCol: *mut c_int) -> c_int;
+  pub fn COPT_GetRowIdx(prob: *mut copt_prob, rowName: *const c_char, p_iRow: *mut c_int) -> c_int;
+  pub fn COPT_GetColName(prob: *mut copt_prob, iCol: c_int, buff: *mut c_char, buffSize: c_int, pReqSize: *mut c_int) -> c_int;
+  pub fn COPT_GetRowName(prob: *mut copt_prob, iRow: c_int, buff: *mut c_char, buffSize: c_int, pReqSize: *mut c_int) -> c_int;
   // ...
   pub fn COPT_GetElem(prob: *mut copt_prob, iCol: c_int, iRow: c_int, p_elem: *mut c_double) -> c_int;
   pub fn COPT_SetElem(prob: *mut copt_prob, iCol: c_int, iRow: c_int, newElem: c_double) -> c_int;
@@ -203,6 +223,13 @@ extern "C" {
   pub fn COPT_SetObjSense(prob: *mut copt_prob, iObjSense: c_int) -> c_int;
   pub fn COPT_SetObjConst(prob: *mut copt_prob, dObjConst: c_double) -> c_int;
 
+  pub fn COPT_SetQuadObj(prob: *mut copt_prob, num: c_int, qMatRow: *const c_int, qMatCol: *const c_int, qMatElem: *const c_double) -> c_int;
+  pub fn COPT_GetQuadObj(prob: *mut copt_prob, p_nQElem: *mut c_int, qMatRow: *mut c_int, qMatCol: *mut c_int, qMatElem: *mut c_double) -> c_int;
+  pub fn COPT_DelQuadObj(prob: *mut copt_prob) -> c_int;
+
+  pub fn COPT_SetPSDObj(prob: *mut copt_prob, iCol: c_int, mat: c_int) -> c_int;
+  pub fn COPT_DelPSDObj(prob: *mut copt_prob) -> c_int;
+
   pub fn COPT_SetColObj(prob: *mut copt_prob, num: c_int, list: *const c_int, obj: *const c_double) -> c_int;
   pub fn COPT_SetColType(prob: *mut copt_prob, num: c_int, list: *const c_int, types: *const c_char) -> c_int;
   pub fn COPT_SetColLower(prob: *mut copt_prob, num: c_int, list: *const c_int, lower: *const c_double) -> c_int;
@@ -251,6 +278,8 @@ extern "C" {
   //...
   pub fn COPT_GetSolution(prob: *mut copt_prob, colVal: *mut c_double) -> c_int;
   pub fn COPT_GetLpSolution(prob: *mut copt_prob, value: *mut c_double, slack: *mut c_double, rowDual: *mut c_double, redCost: *mut c_double) -> c_int;
+  pub fn COPT_GetPSDSolution(prob: *mut copt_prob, psdColValue: *mut c_double, psdRowSlack: *mut c_double, psdRowDual: *mut c_double, psdColDual: *mut c_double) -> c_int;
+  pub fn COPT_GetLMISolution(prob: *mut copt_prob, lmiSlack: *mut c_double, lmiDual: *mut c_double) -> c_int;
   pub fn COPT_SetLpSolution(prob: *mut copt_prob, value: *const c_double, slack: *const c_double, rowDual: *const c_double, redCost: *const c_double) -> c_int;
   pub fn COPT_GetBasis(prob: *mut copt_prob, colBasis: *mut c_int, rowBasis: *mut c_int) -> c_int;
   pub fn COPT_SetBasis(prob: *mut copt_prob, colBasis: *const c_int, rowBasis: *const c_int) -> c_int;
@@ -282,5 +311,9 @@ extern "C" {
   //...
   pub fn COPT_SetCallback(prob: *mut copt_prob,cb: Option<extern "C" fn(*mut copt_prob, *mut c_void, c_int, *mut c_void)->c_int>,cbctx: c_int, userdata: *mut c_void) -> c_int;
   //...
+  pub fn COPT_GetCallbackInfo(cbData: *mut c_void, cbInfo: *const c_char, p_val: *mut c_void) -> c_int;
+  pub fn COPT_AddCallbackLazyConstr(cbData: *mut c_void, nRowMatCnt: c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, cRowSense: c_char, dRowBound: c_double) -> c_int;
+  pub fn COPT_AddCallbackUserCut(cbData: *mut c_void, nRowMatCnt: c_int, rowMatIdx: *const c_int, rowMatElem: *const c_double, cRowSense: c_char, dRowBound: c_double) -> c_int;
+  //...
   pub fn COPT_Interrupt(prob: *mut copt_prob) -> c_int;
 }
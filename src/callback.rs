@@ -1,3 +1,52 @@
 pub struct LogCallbackParams<'a> {
     pub msg: &'a str
 }
+
+/// Point of the solve at which a `Callback` is invoked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallbackContext {
+    /// An LP relaxation of a MIP node has been solved.
+    MipRelax = 1,
+    /// A new MIP solution candidate has been found.
+    MipSol = 2,
+    /// A MIP node is being processed.
+    MipNode = 4,
+    /// The incumbent solution has been improved.
+    IncumbentSol = 8,
+}
+
+impl CallbackContext {
+    pub(crate) fn from_raw(raw: std::ffi::c_int) -> Option<CallbackContext> {
+        [
+            CallbackContext::MipRelax,
+            CallbackContext::MipSol,
+            CallbackContext::MipNode,
+            CallbackContext::IncumbentSol,
+        ]
+        .into_iter()
+        .find(|&context| context as std::ffi::c_int == raw)
+    }
+}
+
+impl From<CallbackContext> for std::ffi::c_int {
+    fn from(context: CallbackContext) -> std::ffi::c_int {
+        context as std::ffi::c_int
+    }
+}
+
+/// A solver callback, see `Model::optimize_with_callback`.
+///
+/// Returning an error stops the solve and makes `optimize_with_callback` return that error. A
+/// panic does the same with `Error::CallbackPanic`.
+pub trait Callback {
+    fn callback(&mut self, ctx: &mut crate::model::CallbackCtx) -> crate::error::Result<()>;
+}
+
+impl<F> Callback for F
+where
+    F: FnMut(&mut crate::model::CallbackCtx) -> crate::error::Result<()>,
+{
+    fn callback(&mut self, ctx: &mut crate::model::CallbackCtx) -> crate::error::Result<()> {
+        self(ctx)
+    }
+}
@@ -11,6 +11,27 @@ pub enum Error {
 
   /// Inconsistent argument dimensions
   InconsitentDims,
+
+  /// A handle was passed to a model other than the one that created it
+  ModelMismatch,
+
+  /// A handle refers to an object that has been removed from its model
+  RemovedHandle,
+
+  /// An argument value is out of its valid range
+  InvalidArgument,
+
+  /// The operation needs a single right-hand side, which range constraints do not have
+  RangeConstr,
+
+  /// COPT reported a constraint sense this crate has no `ConstrSense` for
+  UnknownSense,
+
+  /// A cone dual cannot be recovered from the reduced costs of its variables
+  ConeDualUnavailable,
+
+  /// A solver callback panicked, so the solve was stopped
+  CallbackPanic,
 }
 
 impl From<std::ffi::NulError> for Error {
@@ -22,6 +43,13 @@ impl From<std::ffi::NulError> for Error {
       match *self {
         Error::FromAPI(code) => write!(f, "Error code from API: {}",code),
         Error::InconsitentDims => write!(f, "Inconsistent argument dimensions"),
+        Error::ModelMismatch => write!(f, "Handle belongs to a different model"),
+        Error::RemovedHandle => write!(f, "Handle refers to a removed object"),
+        Error::InvalidArgument => write!(f, "Invalid argument value"),
+        Error::RangeConstr => write!(f, "Operation not supported for range constraints"),
+        Error::UnknownSense => write!(f, "Unknown constraint sense"),
+        Error::ConeDualUnavailable => write!(f, "Cone dual not available from reduced costs"),
+        Error::CallbackPanic => write!(f, "Solver callback panicked"),
         Error::NulError(ref err) => write!(f, "NulError: {}", err),
       }
     }
@@ -33,6 +61,13 @@ impl From<std::ffi::NulError> for Error {
         Error::FromAPI(..) => "error from C API",
         Error::NulError(ref err) => err.description(),
         Error::InconsitentDims => "Inconsistent argument dimensions",
+        Error::ModelMismatch => "Handle belongs to a different model",
+        Error::RemovedHandle => "Handle refers to a removed object",
+        Error::InvalidArgument => "Invalid argument value",
+        Error::RangeConstr => "Operation not supported for range constraints",
+        Error::UnknownSense => "Unknown constraint sense",
+        Error::ConeDualUnavailable => "Cone dual not available from reduced costs",
+        Error::CallbackPanic => "Solver callback panicked",
       }
     }
   }
@@ -1,7 +1,7 @@
 use std::iter::Sum;
 use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
 
-use crate::model::Var;
+use crate::model::{PsdVar, SymMat, Var};
 
 #[derive(Debug, Clone, Default)]
 pub struct LinExpr {
@@ -28,9 +28,9 @@ impl From<f64> for LinExpr {
     }
 }
 
-impl Into<(Vec<i32>, Vec<f64>, f64)> for LinExpr {
-    fn into(self) -> (Vec<i32>, Vec<f64>, f64) {
-        (self.vars.iter().map(|v| v.0).collect(), self.coeffs, self.offset)
+impl Into<(Vec<Var>, Vec<f64>, f64)> for LinExpr {
+    fn into(self) -> (Vec<Var>, Vec<f64>, f64) {
+        (self.vars, self.coeffs, self.offset)
     }
 }
 
@@ -79,19 +79,19 @@ impl Add for Var {
 impl<'a> Add<&'a Var> for Var {
     type Output = LinExpr;
     fn add(self, rhs: &Var) -> LinExpr {
-        LinExpr::new().add_term(1.0, self).add_term(1.0, rhs.clone())
+        LinExpr::new().add_term(1.0, self).add_term(1.0, *rhs)
     }
 }
 impl<'a> Add<Var> for &'a Var {
     type Output = LinExpr;
     fn add(self, rhs: Var) -> LinExpr {
-        LinExpr::new().add_term(1.0, self.clone()).add_term(1.0, rhs)
+        LinExpr::new().add_term(1.0, *self).add_term(1.0, rhs)
     }
 }
 impl<'a, 'b> Add<&'b Var> for &'a Var {
     type Output = LinExpr;
     fn add(self, rhs: &Var) -> LinExpr {
-        LinExpr::new().add_term(1.0, self.clone()).add_term(1.0, rhs.clone())
+        LinExpr::new().add_term(1.0, *self).add_term(1.0, *rhs)
     }
 }
 impl Add<f64> for Var {
@@ -103,7 +103,7 @@ impl Add<f64> for Var {
 impl<'a> Add<f64> for &'a Var {
     type Output = LinExpr;
     fn add(self, rhs: f64) -> LinExpr {
-        LinExpr::new() + self.clone() + rhs
+        LinExpr::new() + *self + rhs
     }
 }
 
@@ -117,19 +117,19 @@ impl Sub for Var {
 impl<'a> Sub<&'a Var> for Var {
     type Output = LinExpr;
     fn sub(self, rhs: &Var) -> LinExpr {
-        LinExpr::new().add_term(1.0, self).add_term(-1.0, rhs.clone())
+        LinExpr::new().add_term(1.0, self).add_term(-1.0, *rhs)
     }
 }
 impl<'a> Sub<Var> for &'a Var {
     type Output = LinExpr;
     fn sub(self, rhs: Var) -> LinExpr {
-        LinExpr::new().add_term(1.0, self.clone()).add_term(-1.0, rhs)
+        LinExpr::new().add_term(1.0, *self).add_term(-1.0, rhs)
     }
 }
 impl<'a, 'b> Sub<&'b Var> for &'a Var {
     type Output = LinExpr;
     fn sub(self, rhs: &Var) -> LinExpr {
-        LinExpr::new().add_term(1.0, self.clone()).add_term(-1.0, rhs.clone())
+        LinExpr::new().add_term(1.0, *self).add_term(-1.0, *rhs)
     }
 }
 impl Sub<LinExpr> for Var {
@@ -141,7 +141,7 @@ impl Sub<LinExpr> for Var {
 impl<'a> Sub<LinExpr> for &'a Var {
     type Output = LinExpr;
     fn sub(self, expr: LinExpr) -> LinExpr {
-        self.clone() + (-expr)
+        *self + (-expr)
     }
 }
 impl Sub<Var> for f64 {
@@ -153,7 +153,7 @@ impl Sub<Var> for f64 {
 impl<'a> Sub<&'a Var> for f64 {
     type Output = LinExpr;
     fn sub(self, rhs: &Var) -> LinExpr {
-        LinExpr::new() + self + (-rhs.clone())
+        LinExpr::new() + self + (-*rhs)
     }
 }
 
@@ -167,7 +167,7 @@ impl Neg for Var {
 impl<'a> Neg for &'a Var {
     type Output = LinExpr;
     fn neg(self) -> LinExpr {
-        LinExpr::new().add_term(-1.0, self.clone())
+        LinExpr::new().add_term(-1.0, *self)
     }
 }
 
@@ -181,7 +181,7 @@ impl Mul<f64> for Var {
 impl<'a> Mul<f64> for &'a Var {
     type Output = LinExpr;
     fn mul(self, rhs: f64) -> Self::Output {
-        LinExpr::new().add_term(rhs, self.clone())
+        LinExpr::new().add_term(rhs, *self)
     }
 }
 impl Mul<Var> for f64 {
@@ -193,7 +193,7 @@ impl Mul<Var> for f64 {
 impl<'a> Mul<&'a Var> for f64 {
     type Output = LinExpr;
     fn mul(self, rhs: &'a Var) -> Self::Output {
-        LinExpr::new().add_term(self, rhs.clone())
+        LinExpr::new().add_term(self, *rhs)
     }
 }
 
@@ -207,7 +207,7 @@ impl Add<LinExpr> for Var {
 impl<'a> Add<LinExpr> for &'a Var {
     type Output = LinExpr;
     fn add(self, rhs: LinExpr) -> LinExpr {
-        rhs.add_term(1.0, self.clone())
+        rhs.add_term(1.0, *self)
     }
 }
 impl Add<Var> for LinExpr {
@@ -219,7 +219,7 @@ impl Add<Var> for LinExpr {
 impl<'a> Add<&'a Var> for LinExpr {
     type Output = LinExpr;
     fn add(self, rhs: &'a Var) -> LinExpr {
-        self.add_term(1.0, rhs.clone())
+        self.add_term(1.0, *rhs)
     }
 }
 
@@ -334,3 +334,434 @@ impl Sum for LinExpr {
         iter.fold(LinExpr::new(), |acc, expr| acc + expr)
     }
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct QuadExpr {
+    rows: Vec<Var>,
+    cols: Vec<Var>,
+    coeffs: Vec<f64>,
+    lin: LinExpr,
+}
+
+impl From<LinExpr> for QuadExpr {
+    fn from(lin: LinExpr) -> QuadExpr {
+        QuadExpr { lin, ..QuadExpr::default() }
+    }
+}
+
+impl From<Var> for QuadExpr {
+    fn from(var: Var) -> QuadExpr {
+        QuadExpr::from(LinExpr::from(var))
+    }
+}
+
+impl From<f64> for QuadExpr {
+    fn from(offset: f64) -> QuadExpr {
+        QuadExpr::from(LinExpr::from(offset))
+    }
+}
+
+impl From<QuadExpr> for (Vec<Var>, Vec<Var>, Vec<f64>, LinExpr) {
+    fn from(expr: QuadExpr) -> (Vec<Var>, Vec<Var>, Vec<f64>, LinExpr) {
+        (expr.rows, expr.cols, expr.coeffs, expr.lin)
+    }
+}
+
+impl QuadExpr {
+    /// Create an empty quadratic expression.
+    pub fn new() -> Self {
+        QuadExpr::default()
+    }
+
+    /// Add a quadratic term `coeff * row * col` into the expression.
+    pub fn add_term(mut self, coeff: f64, row: Var, col: Var) -> Self {
+        self.coeffs.push(coeff);
+        self.rows.push(row);
+        self.cols.push(col);
+        self
+    }
+
+    /// Add quadratic terms into the expression. Panics if the lengths do not match.
+    pub fn add_terms(mut self, coeffs: &[f64], rows: &[Var], cols: &[Var]) -> Self {
+        assert_eq!(coeffs.len(), rows.len());
+        assert_eq!(coeffs.len(), cols.len());
+        self.coeffs.extend_from_slice(coeffs);
+        self.rows.extend_from_slice(rows);
+        self.cols.extend_from_slice(cols);
+        self
+    }
+
+    /// Add a linear term into the expression.
+    pub fn add_lin_term(mut self, coeff: f64, var: Var) -> Self {
+        self.lin = self.lin.add_term(coeff, var);
+        self
+    }
+
+    /// Add a constant into the expression.
+    pub fn add_constant(mut self, constant: f64) -> Self {
+        self.lin = self.lin.add_constant(constant);
+        self
+    }
+}
+
+/// `Var` * `Var` => `QuadExpr`
+impl Mul for Var {
+    type Output = QuadExpr;
+    fn mul(self, rhs: Var) -> QuadExpr {
+        QuadExpr::new().add_term(1.0, self, rhs)
+    }
+}
+impl Mul<&Var> for Var {
+    type Output = QuadExpr;
+    fn mul(self, rhs: &Var) -> QuadExpr {
+        QuadExpr::new().add_term(1.0, self, *rhs)
+    }
+}
+impl Mul<Var> for &Var {
+    type Output = QuadExpr;
+    fn mul(self, rhs: Var) -> QuadExpr {
+        QuadExpr::new().add_term(1.0, *self, rhs)
+    }
+}
+impl Mul<&Var> for &Var {
+    type Output = QuadExpr;
+    fn mul(self, rhs: &Var) -> QuadExpr {
+        QuadExpr::new().add_term(1.0, *self, *rhs)
+    }
+}
+
+/// `LinExpr` * `LinExpr` => `QuadExpr`
+impl Mul for LinExpr {
+    type Output = QuadExpr;
+    fn mul(self, rhs: LinExpr) -> QuadExpr {
+        let mut expr = QuadExpr::new();
+        for (lvar, &lcoeff) in self.vars.iter().zip(self.coeffs.iter()) {
+            for (rvar, &rcoeff) in rhs.vars.iter().zip(rhs.coeffs.iter()) {
+                expr = expr.add_term(lcoeff * rcoeff, *lvar, *rvar);
+            }
+        }
+        let (loffset, roffset) = (self.offset, rhs.offset);
+        expr.lin = self * roffset + rhs * loffset - loffset * roffset;
+        expr
+    }
+}
+
+/// `Var` * `LinExpr` => `QuadExpr`
+impl Mul<LinExpr> for Var {
+    type Output = QuadExpr;
+    fn mul(self, rhs: LinExpr) -> QuadExpr {
+        LinExpr::from(self) * rhs
+    }
+}
+impl Mul<Var> for LinExpr {
+    type Output = QuadExpr;
+    fn mul(self, rhs: Var) -> QuadExpr {
+        self * LinExpr::from(rhs)
+    }
+}
+
+/// `QuadExpr` + `LinExpr` => `QuadExpr`
+impl Add<LinExpr> for QuadExpr {
+    type Output = QuadExpr;
+    fn add(mut self, rhs: LinExpr) -> QuadExpr {
+        self.lin += rhs;
+        self
+    }
+}
+impl Add<QuadExpr> for LinExpr {
+    type Output = QuadExpr;
+    fn add(self, rhs: QuadExpr) -> QuadExpr {
+        rhs + self
+    }
+}
+
+/// `QuadExpr` - `LinExpr` => `QuadExpr`
+impl Sub<LinExpr> for QuadExpr {
+    type Output = QuadExpr;
+    fn sub(self, rhs: LinExpr) -> QuadExpr {
+        self + (-rhs)
+    }
+}
+impl Sub<QuadExpr> for LinExpr {
+    type Output = QuadExpr;
+    fn sub(self, rhs: QuadExpr) -> QuadExpr {
+        self + (-rhs)
+    }
+}
+
+/// `QuadExpr` + `Var` => `QuadExpr`
+impl Add<Var> for QuadExpr {
+    type Output = QuadExpr;
+    fn add(self, rhs: Var) -> QuadExpr {
+        self.add_lin_term(1.0, rhs)
+    }
+}
+impl Sub<Var> for QuadExpr {
+    type Output = QuadExpr;
+    fn sub(self, rhs: Var) -> QuadExpr {
+        self.add_lin_term(-1.0, rhs)
+    }
+}
+
+/// `QuadExpr` + `f64` => `QuadExpr`
+impl Add<f64> for QuadExpr {
+    type Output = QuadExpr;
+    fn add(self, rhs: f64) -> QuadExpr {
+        self.add_constant(rhs)
+    }
+}
+impl Sub<f64> for QuadExpr {
+    type Output = QuadExpr;
+    fn sub(self, rhs: f64) -> QuadExpr {
+        self.add_constant(-rhs)
+    }
+}
+
+impl Add for QuadExpr {
+    type Output = QuadExpr;
+    fn add(mut self, rhs: QuadExpr) -> QuadExpr {
+        self += rhs;
+        self
+    }
+}
+
+impl Sub for QuadExpr {
+    type Output = QuadExpr;
+    fn sub(self, rhs: QuadExpr) -> QuadExpr {
+        self + (-rhs)
+    }
+}
+
+impl Neg for QuadExpr {
+    type Output = QuadExpr;
+    fn neg(mut self) -> QuadExpr {
+        for coeff in &mut self.coeffs {
+            *coeff = -*coeff;
+        }
+        self.lin = -self.lin;
+        self
+    }
+}
+
+impl AddAssign for QuadExpr {
+    fn add_assign(&mut self, rhs: QuadExpr) {
+        for ((row, col), coeff) in rhs.rows.into_iter().zip(rhs.cols).zip(rhs.coeffs) {
+            let same = |i: &usize| self.rows[*i] == row && self.cols[*i] == col;
+            if let Some(idx) = (0..self.coeffs.len()).find(same) {
+                self.coeffs[idx] += coeff;
+            } else {
+                self.rows.push(row);
+                self.cols.push(col);
+                self.coeffs.push(coeff);
+            }
+        }
+        self.lin += rhs.lin;
+    }
+}
+
+impl AddAssign<LinExpr> for QuadExpr {
+    fn add_assign(&mut self, rhs: LinExpr) {
+        self.lin += rhs;
+    }
+}
+
+impl Mul<f64> for QuadExpr {
+    type Output = QuadExpr;
+    fn mul(mut self, rhs: f64) -> QuadExpr {
+        for coeff in &mut self.coeffs {
+            *coeff *= rhs;
+        }
+        self.lin = self.lin * rhs;
+        self
+    }
+}
+
+impl Mul<QuadExpr> for f64 {
+    type Output = QuadExpr;
+    fn mul(self, rhs: QuadExpr) -> QuadExpr {
+        rhs * self
+    }
+}
+
+impl Sum for QuadExpr {
+    fn sum<I: Iterator<Item = QuadExpr>>(iter: I) -> QuadExpr {
+        iter.fold(QuadExpr::new(), |acc, expr| acc + expr)
+    }
+}
+
+/// A linear expression plus inner products `<mat, psd_var>` of symmetric matrices with PSD
+/// variables.
+#[derive(Debug, Clone, Default)]
+pub struct PsdExpr {
+    psd_vars: Vec<PsdVar>,
+    mats: Vec<SymMat>,
+    lin: LinExpr,
+}
+
+impl From<LinExpr> for PsdExpr {
+    fn from(lin: LinExpr) -> PsdExpr {
+        PsdExpr { lin, ..PsdExpr::default() }
+    }
+}
+
+impl From<PsdExpr> for (Vec<PsdVar>, Vec<SymMat>, LinExpr) {
+    fn from(expr: PsdExpr) -> (Vec<PsdVar>, Vec<SymMat>, LinExpr) {
+        (expr.psd_vars, expr.mats, expr.lin)
+    }
+}
+
+impl PsdExpr {
+    /// Create an empty expression.
+    pub fn new() -> Self {
+        PsdExpr::default()
+    }
+
+    /// Add the inner product `<mat, psd_var>` into the expression.
+    pub fn add_term(mut self, mat: SymMat, psd_var: PsdVar) -> Self {
+        self.mats.push(mat);
+        self.psd_vars.push(psd_var);
+        self
+    }
+
+    /// Add a linear term into the expression.
+    pub fn add_lin_term(mut self, coeff: f64, var: Var) -> Self {
+        self.lin = self.lin.add_term(coeff, var);
+        self
+    }
+
+    /// Add a constant into the expression.
+    pub fn add_constant(mut self, constant: f64) -> Self {
+        self.lin = self.lin.add_constant(constant);
+        self
+    }
+}
+
+/// `PsdExpr` + `LinExpr` => `PsdExpr`
+impl Add<LinExpr> for PsdExpr {
+    type Output = PsdExpr;
+    fn add(mut self, rhs: LinExpr) -> PsdExpr {
+        self.lin += rhs;
+        self
+    }
+}
+impl Add<PsdExpr> for LinExpr {
+    type Output = PsdExpr;
+    fn add(self, rhs: PsdExpr) -> PsdExpr {
+        rhs + self
+    }
+}
+
+impl Add for PsdExpr {
+    type Output = PsdExpr;
+    fn add(mut self, rhs: PsdExpr) -> PsdExpr {
+        self.psd_vars.extend(rhs.psd_vars);
+        self.mats.extend(rhs.mats);
+        self.lin += rhs.lin;
+        self
+    }
+}
+
+/// A matrix expression `sum_i var_i * mat_i + constant` of scalar variables and symmetric matrices,
+/// used to state linear matrix inequalities.
+#[derive(Debug, Clone, Default)]
+pub struct LmiExpr {
+    vars: Vec<Var>,
+    mats: Vec<SymMat>,
+    constant: Option<SymMat>,
+}
+
+impl From<LmiExpr> for (Vec<Var>, Vec<SymMat>, Option<SymMat>) {
+    fn from(expr: LmiExpr) -> (Vec<Var>, Vec<SymMat>, Option<SymMat>) {
+        (expr.vars, expr.mats, expr.constant)
+    }
+}
+
+impl LmiExpr {
+    /// Create an empty expression.
+    pub fn new() -> Self {
+        LmiExpr::default()
+    }
+
+    /// Add the term `var * mat` into the expression.
+    pub fn add_term(mut self, var: Var, mat: SymMat) -> Self {
+        self.vars.push(var);
+        self.mats.push(mat);
+        self
+    }
+
+    /// Set the constant matrix of the expression.
+    pub fn set_constant(mut self, mat: SymMat) -> Self {
+        self.constant = Some(mat);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handle::Handle;
+
+    fn var(k: u64) -> Var {
+        Var::new(0, k)
+    }
+
+    fn eval_lin(expr: LinExpr, x: &[f64]) -> f64 {
+        let (vars, coeffs, offset) = expr.into();
+        vars.iter().zip(coeffs).map(|(v, c)| c * x[v.serial() as usize]).sum::<f64>() + offset
+    }
+
+    fn eval(expr: QuadExpr, x: &[f64]) -> f64 {
+        let (rows, cols, coeffs, lin) = expr.into();
+        let quad: f64 = (0..coeffs.len())
+            .map(|k| coeffs[k] * x[rows[k].serial() as usize] * x[cols[k].serial() as usize])
+            .sum();
+        quad + eval_lin(lin, x)
+    }
+
+    const POINTS: [[f64; 2]; 3] = [[0.0, 0.0], [1.5, -2.0], [-3.0, 0.25]];
+
+    #[test]
+    fn lin_times_lin_keeps_offset_cross_terms() {
+        let (x0, x1) = (var(0), var(1));
+        let lhs = || LinExpr::new().add_term(1.0, x0).add_constant(2.0);
+        let rhs = || LinExpr::new().add_term(3.0, x1).add_constant(-1.0);
+        for x in &POINTS {
+            let expected = eval_lin(lhs(), x) * eval_lin(rhs(), x);
+            assert_eq!(eval(lhs() * rhs(), x), expected);
+        }
+    }
+
+    #[test]
+    fn add_assign_merges_duplicate_terms() {
+        let (x0, x1) = (var(0), var(1));
+        let mut expr = x0 * x1 + x1 * x1;
+        expr += 2.0 * (x0 * x1);
+        let (rows, cols, coeffs, _) = expr.into();
+        assert_eq!(rows, vec![x0, x1]);
+        assert_eq!(cols, vec![x1, x1]);
+        assert_eq!(coeffs, vec![3.0, 1.0]);
+    }
+
+    #[test]
+    fn neg_flips_every_sign() {
+        let (x0, x1) = (var(0), var(1));
+        let expr = || x0 * x1 + x0 + 4.0;
+        for x in &POINTS {
+            assert_eq!(eval(-expr(), x), -eval(expr(), x));
+        }
+    }
+
+    #[test]
+    fn sub_mixes_quadratic_and_linear_parts() {
+        let (x0, x1) = (var(0), var(1));
+        let quad = || (x0 * x0).add_lin_term(2.0, x1).add_constant(1.0);
+        let lin = || LinExpr::new().add_term(5.0, x0).add_constant(3.0);
+        for x in &POINTS {
+            assert_eq!(eval(quad() - lin(), x), eval(quad(), x) - eval_lin(lin(), x));
+            assert_eq!(eval(lin() - quad(), x), eval_lin(lin(), x) - eval(quad(), x));
+            assert_eq!(eval(quad() - quad(), x), 0.0);
+        }
+        let (_, _, coeffs, _) = (quad() - quad()).into();
+        assert_eq!(coeffs, vec![0.0]);
+    }
+}
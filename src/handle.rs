@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Common interface of the handles a `Model` hands out.
+pub trait Handle: Copy {
+    fn new(model_id: u32, serial: u64) -> Self;
+    fn model_id(&self) -> u32;
+    fn serial(&self) -> u64;
+}
+
+/// Maps the serial numbers carried by handles to the current COPT indices.
+///
+/// COPT shifts every later index when objects are deleted, so handles keep a serial number that
+/// never changes and the model translates it through this map. Clones of a map share the serial
+/// counter, so copies of a model never hand out the same serial for different objects.
+#[derive(Debug, Clone, Default)]
+pub struct HandleMap {
+    /// Current index of each live serial. Removed serials are dropped, so the map only grows with
+    /// the number of live objects.
+    indices: HashMap<u64, i32>,
+    /// Serial of the object at each index.
+    serials: Vec<u64>,
+    /// Next serial to hand out, shared with all clones of this map.
+    next_serial: Arc<AtomicU64>,
+}
+
+impl HandleMap {
+    /// Number of objects currently alive.
+    pub fn len(&self) -> usize {
+        self.serials.len()
+    }
+
+    /// Register a new object at the end and return its serial.
+    pub fn push(&mut self) -> u64 {
+        let serial = self.next_serial.fetch_add(1, Ordering::Relaxed);
+        self.indices.insert(serial, self.serials.len() as i32);
+        self.serials.push(serial);
+        serial
+    }
+
+    /// Current index of a serial, or `None` if the object has been removed.
+    pub fn index(&self, serial: u64) -> Option<i32> {
+        self.indices.get(&serial).copied()
+    }
+
+    /// Serial of the object at the given index.
+    pub fn serial(&self, index: i32) -> u64 {
+        self.serials[index as usize]
+    }
+
+    /// Forget all current objects and register `len` new ones in their place.
+    pub fn reset(&mut self, len: usize) {
+        self.indices.clear();
+        self.serials.clear();
+        for _ in 0..len {
+            self.push();
+        }
+    }
+
+    /// Forget the objects at the given indices and shift the remaining ones down.
+    pub fn remove(&mut self, indices: &[i32]) {
+        let mut removed = vec![false; self.serials.len()];
+        for &index in indices {
+            removed[index as usize] = true;
+        }
+        let serials = std::mem::take(&mut self.serials);
+        for (index, serial) in serials.into_iter().enumerate() {
+            if removed[index] {
+                self.indices.remove(&serial);
+            } else {
+                self.indices.insert(serial, self.serials.len() as i32);
+                self.serials.push(serial);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map_with(len: usize) -> (HandleMap, Vec<u64>) {
+        let mut map = HandleMap::default();
+        let serials = (0..len).map(|_| map.push()).collect();
+        (map, serials)
+    }
+
+    #[test]
+    fn push_assigns_consecutive_indices() {
+        let (map, serials) = map_with(3);
+        assert_eq!(map.len(), 3);
+        for (index, &serial) in serials.iter().enumerate() {
+            assert_eq!(map.index(serial), Some(index as i32));
+            assert_eq!(map.serial(index as i32), serial);
+        }
+    }
+
+    #[test]
+    fn remove_shifts_later_indices_down() {
+        let (mut map, s) = map_with(5);
+        map.remove(&[1, 3]);
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.index(s[0]), Some(0));
+        assert_eq!(map.index(s[1]), None);
+        assert_eq!(map.index(s[2]), Some(1));
+        assert_eq!(map.index(s[3]), None);
+        assert_eq!(map.index(s[4]), Some(2));
+        assert_eq!(map.serial(2), s[4]);
+    }
+
+    #[test]
+    fn remove_accepts_unsorted_and_duplicate_indices() {
+        let (mut map, s) = map_with(4);
+        map.remove(&[2, 0, 2]);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.index(s[0]), None);
+        assert_eq!(map.index(s[1]), Some(0));
+        assert_eq!(map.index(s[2]), None);
+        assert_eq!(map.index(s[3]), Some(1));
+    }
+
+    #[test]
+    fn push_after_remove_appends() {
+        let (mut map, s) = map_with(3);
+        map.remove(&[0]);
+        let serial = map.push();
+        assert!(!s.contains(&serial));
+        assert_eq!(map.index(serial), Some(2));
+        assert_eq!(map.index(s[0]), None);
+    }
+
+    #[test]
+    fn reset_invalidates_old_serials() {
+        let (mut map, s) = map_with(3);
+        map.reset(2);
+        assert_eq!(map.len(), 2);
+        assert!(s.iter().all(|&serial| map.index(serial).is_none()));
+        assert_eq!(map.indices.len(), 2);
+        let fresh = [map.serial(0), map.serial(1)];
+        assert!(fresh.iter().all(|serial| !s.contains(serial)));
+        assert_eq!(map.index(fresh[1]), Some(1));
+    }
+
+    #[test]
+    fn clones_never_share_serials() {
+        let (mut map, _) = map_with(2);
+        let mut copy = map.clone();
+        let a = map.push();
+        let b = copy.push();
+        assert_ne!(a, b);
+        assert_eq!(map.index(b), None);
+        assert_eq!(copy.index(a), None);
+    }
+}
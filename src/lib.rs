@@ -2,6 +2,7 @@
 #![cfg_attr(feature="clippy", plugin(clippy))]
 
 mod util;
+mod handle;
 mod error;
 mod param;
 mod attribute;
@@ -13,13 +14,24 @@ mod callback;
 pub use copt_sys::{IntAttr,DoubleAttr,IntParam,DoubleParam};
 pub use error::{Error, Result};
 pub use env::Env;
-pub use expr::LinExpr;
+pub use expr::{LinExpr, LmiExpr, PsdExpr, QuadExpr};
 pub use model::Model;
 pub use model::Var;
+pub use model::Constr;
+pub use model::QConstr;
+pub use model::{Sos, SosType};
+pub use model::Indicator;
+pub use model::{Cone, ConeType};
+pub use model::{ExpCone, ExpConeType};
+pub use model::{PsdConstr, PsdVar, SymMat};
+pub use model::Lmi;
+pub use model::CscProblem;
 pub use model::Status::*;
 pub use model::VarType::*;
 pub use model::ConstrSense::*;
 pub use model::ModelSense::*;
 pub use callback::LogCallbackParams;
+pub use callback::{Callback, CallbackContext};
+pub use model::CallbackCtx;
 
 pub const INFINITY: f64 = 1e30;
\ No newline at end of file
@@ -1,10 +1,15 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::{ffi::CString, mem::transmute, ptr::null_mut};
 
-use copt_sys::COPT_Interrupt;
+use copt_sys::{IntAttr, COPT_Interrupt};
 
 use crate::{
+    callback::CallbackContext,
     env::{Env, EnvAPI},
+    handle::{Handle, HandleMap},
     util,
 };
 
@@ -42,6 +47,42 @@ impl Into<std::ffi::c_char> for ConstrSense {
     }
 }
 
+impl ConstrSense {
+    fn from_raw(sense: std::ffi::c_char) -> Option<ConstrSense> {
+        match sense as u8 {
+            b'E' => Some(ConstrSense::Equal),
+            b'L' => Some(ConstrSense::Less),
+            b'G' => Some(ConstrSense::Greater),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SosType {
+    Sos1 = 1,
+    Sos2 = 2,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConeType {
+    /// `x[0] >= ||x[1..]||`
+    Quad = 1,
+    /// `2 * x[0] * x[1] >= ||x[2..]||^2`
+    RotatedQuad = 2,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExpConeType {
+    /// `x[0] >= x[1] * exp(x[2] / x[1])`, `x[0], x[1] >= 0`
+    Primal = 3,
+    /// `x[0] >= -x[2] * exp(x[1] / x[2] - 1)`, `x[0] >= 0`, `x[2] <= 0`
+    Dual = 4,
+}
+
+/// Row sense that makes COPT read the bound arguments as the lower and upper bounds of a row.
+const BOUNDS_SENSE: std::ffi::c_char = 0;
+
 #[derive(Debug, Copy, Clone)]
 pub enum ModelSense {
     Minimize = 1,
@@ -78,17 +119,105 @@ impl From<i32> for Status {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Var(pub i32);
+/// Source of the ids that tie handles to the `Model` that created them.
+static NEXT_MODEL_ID: AtomicU32 = AtomicU32::new(0);
 
-impl Into<i32> for Var {
-    fn into(self) -> i32 {
-        return self.0;
-    }
+/// Handle of a variable (column) of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Var {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of a linear constraint (row) of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Constr {
+    model_id: u32,
+    serial: u64,
 }
 
-//pub type Var = i32;
-pub type Constr = i32;
+/// Handle of a quadratic constraint of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QConstr {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of an SOS constraint of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sos {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of an indicator constraint of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Indicator {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of a second-order cone constraint of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cone {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of an exponential cone constraint of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExpCone {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of a positive semidefinite matrix variable of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PsdVar {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of a symmetric constant matrix registered with a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SymMat {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of a constraint over PSD variables of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PsdConstr {
+    model_id: u32,
+    serial: u64,
+}
+
+/// Handle of a linear matrix inequality of a `Model`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Lmi {
+    model_id: u32,
+    serial: u64,
+}
+
+macro_rules! impl_handle {
+    ($($t:ident)*) => ($(
+        impl Handle for $t {
+            fn new(model_id: u32, serial: u64) -> $t {
+                $t { model_id, serial }
+            }
+
+            fn model_id(&self) -> u32 {
+                self.model_id
+            }
+
+            fn serial(&self) -> u64 {
+                self.serial
+            }
+        }
+    )*)
+}
+
+impl_handle! { Var Constr QConstr Sos Indicator Cone ExpCone PsdVar SymMat PsdConstr Lmi }
 
 struct LogCallbackData<'a> {
     callback: &'a mut dyn FnMut(crate::callback::LogCallbackParams),
@@ -129,14 +258,265 @@ extern "C" fn terminate_callback_wrapper(prob: *mut copt_sys::copt_prob, cbdata:
     }
 }
 
+struct CallbackData<'a> {
+    model: &'a Model,
+    callback: &'a mut dyn crate::callback::Callback,
+    /// First error returned by the callback, reported once the solve has stopped.
+    error: Option<crate::error::Error>,
+}
+
+extern "C" fn general_callback_wrapper(
+    prob: *mut copt_sys::copt_prob,
+    cbdata: *mut std::ffi::c_void,
+    cbctx: std::ffi::c_int,
+    usrdata: *mut std::ffi::c_void,
+) -> std::ffi::c_int {
+    let usrdata = unsafe { &mut *(usrdata as *mut CallbackData) };
+    let context = match CallbackContext::from_raw(cbctx) {
+        Some(context) => context,
+        None => return 0,
+    };
+    let mut ctx = CallbackCtx { model: usrdata.model, prob, cbdata, context };
+    let callback = &mut usrdata.callback;
+    let result = match catch_unwind(AssertUnwindSafe(|| callback.callback(&mut ctx))) {
+        Ok(result) => result,
+        Err(_) => Err(crate::error::Error::CallbackPanic),
+    };
+    if let Err(error) = result {
+        if usrdata.error.is_none() {
+            usrdata.error = Some(error);
+        }
+        unsafe { COPT_Interrupt(prob) };
+    }
+    0
+}
+
+/// The state of a running solve handed to a `Callback`.
+pub struct CallbackCtx<'a> {
+    model: &'a Model,
+    prob: *mut copt_sys::copt_prob,
+    cbdata: *mut std::ffi::c_void,
+    context: CallbackContext,
+}
+
+impl CallbackCtx<'_> {
+    /// Get the context the callback is invoked in.
+    pub fn context(&self) -> CallbackContext {
+        self.context
+    }
+
+    /// Get the objective value of the best solution found so far.
+    pub fn get_best_obj(&self) -> crate::error::Result<f64> {
+        let mut value = 0.0;
+        self.get_info("BestObj", &mut value as *mut f64 as *mut std::ffi::c_void)?;
+        Ok(value)
+    }
+
+    /// Get the best bound on the objective value found so far.
+    pub fn get_best_bnd(&self) -> crate::error::Result<f64> {
+        let mut value = 0.0;
+        self.get_info("BestBnd", &mut value as *mut f64 as *mut std::ffi::c_void)?;
+        Ok(value)
+    }
+
+    /// Get the number of nodes explored so far.
+    pub fn get_node_cnt(&self) -> crate::error::Result<i32> {
+        let mut value = 0;
+        self.get_info("NodeCnt", &mut value as *mut i32 as *mut std::ffi::c_void)?;
+        Ok(value)
+    }
+
+    /// Check whether an incumbent solution is available.
+    pub fn has_incumbent(&self) -> crate::error::Result<bool> {
+        let mut value = 0;
+        self.get_info("HasIncumbent", &mut value as *mut i32 as *mut std::ffi::c_void)?;
+        Ok(value != 0)
+    }
+
+    /// Get the values of variables in the incumbent solution.
+    pub fn get_incumbent(&self, vars: &[Var]) -> crate::error::Result<Vec<f64>> {
+        self.get_solution("Incumbent", vars)
+    }
+
+    /// Get the values of variables in the new solution candidate, in the `MipSol` context.
+    pub fn get_candidate(&self, vars: &[Var]) -> crate::error::Result<Vec<f64>> {
+        self.get_solution("MipCandidate", vars)
+    }
+
+    /// Get the values of variables in the current LP relaxation, in the `MipRelax` context.
+    pub fn get_relaxation(&self, vars: &[Var]) -> crate::error::Result<Vec<f64>> {
+        self.get_solution("RelaxSolution", vars)
+    }
+
+    /// Add a lazy constraint `expr sense rhs` cutting off the current candidate, in the `MipSol`
+    /// context.
+    pub fn add_lazy_constr(
+        &mut self,
+        expr: crate::expr::LinExpr,
+        sense: ConstrSense,
+        rhs: f64,
+    ) -> crate::error::Result<()> {
+        let (vars, coeffs, offset) = expr.into();
+        let vars = self.model.var_indices(&vars)?;
+        self.model.check_apicall(unsafe {
+            copt_sys::COPT_AddCallbackLazyConstr(
+                self.cbdata,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                sense.into(),
+                rhs - offset,
+            )
+        })
+    }
+
+    /// Add a user cut `expr sense rhs` tightening the current relaxation, in the `MipRelax`
+    /// context.
+    pub fn add_user_cut(
+        &mut self,
+        expr: crate::expr::LinExpr,
+        sense: ConstrSense,
+        rhs: f64,
+    ) -> crate::error::Result<()> {
+        let (vars, coeffs, offset) = expr.into();
+        let vars = self.model.var_indices(&vars)?;
+        self.model.check_apicall(unsafe {
+            copt_sys::COPT_AddCallbackUserCut(
+                self.cbdata,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                sense.into(),
+                rhs - offset,
+            )
+        })
+    }
+
+    /// Stop the solve once the callback returns.
+    pub fn terminate(&self) -> crate::error::Result<()> {
+        self.model.check_apicall(unsafe { COPT_Interrupt(self.prob) })
+    }
+
+    /// Query a full solution vector through `COPT_GetCallbackInfo` and pick the given variables.
+    fn get_solution(&self, name: &str, vars: &[Var]) -> crate::error::Result<Vec<f64>> {
+        let list = self.model.var_indices(vars)?;
+        let mut values = vec![0.0; self.model.vars.len()];
+        self.get_info(name, values.as_mut_ptr() as *mut std::ffi::c_void)?;
+        Ok(list.into_iter().map(|index| values[index as usize]).collect())
+    }
+
+    fn get_info(&self, name: &str, value: *mut std::ffi::c_void) -> crate::error::Result<()> {
+        let name_c = CString::new(name)?;
+        self.model.check_apicall(unsafe {
+            copt_sys::COPT_GetCallbackInfo(self.cbdata, name_c.as_ptr(), value)
+        })
+    }
+}
+
+/// A complete problem in compressed sparse column form, see `Model::load_csc`.
+///
+/// Optional per-column or per-row data (`vtypes`, `lbs`, `ubs`, `col_names`, `row_names`) may be
+/// left empty, in which case COPT's defaults are used.
+#[derive(Debug, Clone)]
+pub struct CscProblem {
+    pub sense: ModelSense,
+    pub obj_const: f64,
+    /// Objective coefficient of each column; its length is the number of columns.
+    pub obj: Vec<f64>,
+    /// Start of each column in `row_ind`/`values`, with one extra trailing entry.
+    pub col_ptr: Vec<i32>,
+    pub row_ind: Vec<i32>,
+    pub values: Vec<f64>,
+    pub vtypes: Vec<VarType>,
+    pub lbs: Vec<f64>,
+    pub ubs: Vec<f64>,
+    /// Sense of each row; its length is the number of rows.
+    pub senses: Vec<ConstrSense>,
+    pub rhs: Vec<f64>,
+    pub col_names: Vec<String>,
+    pub row_names: Vec<String>,
+}
+
+impl CscProblem {
+    fn check_dims(&self) -> crate::error::Result<()> {
+        let (ncols, nrows) = (self.obj.len(), self.senses.len());
+        let optional = |len: usize, expected: usize| len == 0 || len == expected;
+        let consistent = self.col_ptr.len() == ncols + 1
+            && self.col_ptr[0] == 0
+            && self.col_ptr.windows(2).all(|w| w[0] <= w[1])
+            && self.col_ptr[ncols] as usize == self.row_ind.len()
+            && self.row_ind.len() == self.values.len()
+            && self.row_ind.iter().all(|&row| row >= 0 && (row as usize) < nrows)
+            && self.rhs.len() == nrows
+            && optional(self.vtypes.len(), ncols)
+            && optional(self.lbs.len(), ncols)
+            && optional(self.ubs.len(), ncols)
+            && optional(self.col_names.len(), ncols)
+            && optional(self.row_names.len(), nrows);
+        if !consistent {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        Ok(())
+    }
+}
+
+/// Pointer to the data of an optional argument, or null when it is empty.
+fn ptr_or_null<T>(values: &[T]) -> *const T {
+    if values.is_empty() { std::ptr::null() } else { values.as_ptr() }
+}
+
+/// Solution of the PSD part of a problem, see `Model::get_psd_solution`.
+struct PsdSolution {
+    /// Start of each PSD variable in `values` and `col_duals`, with one extra trailing entry.
+    offsets: Vec<usize>,
+    values: Vec<f64>,
+    col_duals: Vec<f64>,
+    row_slacks: Vec<f64>,
+    row_duals: Vec<f64>,
+}
+
+/// Rows or columns fetched from COPT in compressed sparse form.
+struct SparseMatrix {
+    beg: Vec<i32>,
+    cnt: Vec<i32>,
+    ind: Vec<i32>,
+    val: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// Indices and values of the `k`-th row or column.
+    fn entries(&self, k: usize) -> (&[i32], &[f64]) {
+        let range = self.beg[k] as usize..(self.beg[k] + self.cnt[k]) as usize;
+        (&self.ind[range.clone()], &self.val[range])
+    }
+}
+
 // #[allow(unused_variables)]
 // extern "C" fn null_log_callback_wrapper(msg: copt_sys::c_str, usrdata: *mut std::ffi::c_void) {}
 
 pub struct Model {
     model: *mut copt_sys::copt_prob,
     env: Env,
-    var_count: i32,
-    constr_count: i32,
+    id: u32,
+    vars: HandleMap,
+    constrs: HandleMap,
+    qconstrs: HandleMap,
+    soss: HandleMap,
+    indicators: HandleMap,
+    cones: HandleMap,
+    /// Variables of the second-order cones added through `add_cone`, by serial.
+    cone_vars: HashMap<u64, Vec<Var>>,
+    exp_cones: HandleMap,
+    /// Variables of the exponential cones added through `add_exp_cone`, by serial.
+    exp_cone_vars: HashMap<u64, [Var; 3]>,
+    psd_vars: HandleMap,
+    sym_mats: HandleMap,
+    psd_constrs: HandleMap,
+    lmis: HandleMap,
+    /// Serials of variables already looked up by name.
+    var_names: RefCell<HashMap<String, u64>>,
+    /// Serials of constraints already looked up by name.
+    constr_names: RefCell<HashMap<String, u64>>,
 }
 
 impl Model {
@@ -144,10 +524,112 @@ impl Model {
         let mut model = null_mut();
         env.check_apicall(unsafe { copt_sys::COPT_CreateProb(env.get_ptr(), &mut model) })?;
         let env_copy = Env::shallow_copy(env);
-        let model = Model { model, env: env_copy, var_count: 0, constr_count: 0 };
+        let id = NEXT_MODEL_ID.fetch_add(1, Ordering::Relaxed);
+        let model = Model {
+            model,
+            env: env_copy,
+            id,
+            vars: HandleMap::default(),
+            constrs: HandleMap::default(),
+            qconstrs: HandleMap::default(),
+            soss: HandleMap::default(),
+            indicators: HandleMap::default(),
+            cones: HandleMap::default(),
+            cone_vars: HashMap::new(),
+            exp_cones: HandleMap::default(),
+            exp_cone_vars: HashMap::new(),
+            psd_vars: HandleMap::default(),
+            sym_mats: HandleMap::default(),
+            psd_constrs: HandleMap::default(),
+            lmis: HandleMap::default(),
+            var_names: RefCell::default(),
+            constr_names: RefCell::default(),
+        };
+        Ok(model)
+    }
+
+    /// Create an independent copy of the model through `COPT_CreateCopy`.
+    ///
+    /// The copy shares the environment and accepts the handles of this model that exist at the
+    /// time of copying. Objects added to either model afterwards are not visible in the other.
+    ///
+    /// `Model` wraps a raw COPT pointer and borrows the environment without owning it, so it is
+    /// not `Send`: copies can be modified and solved one after another, but not moved to other
+    /// threads to be solved in parallel.
+    pub fn try_clone(&self) -> crate::error::Result<Model> {
+        let mut model = null_mut();
+        self.check_apicall(unsafe { copt_sys::COPT_CreateCopy(self.model, &mut model) })?;
+        Ok(Model {
+            model,
+            env: Env::shallow_copy(&self.env),
+            id: self.id,
+            vars: self.vars.clone(),
+            constrs: self.constrs.clone(),
+            qconstrs: self.qconstrs.clone(),
+            soss: self.soss.clone(),
+            indicators: self.indicators.clone(),
+            cones: self.cones.clone(),
+            cone_vars: self.cone_vars.clone(),
+            exp_cones: self.exp_cones.clone(),
+            exp_cone_vars: self.exp_cone_vars.clone(),
+            psd_vars: self.psd_vars.clone(),
+            sym_mats: self.sym_mats.clone(),
+            psd_constrs: self.psd_constrs.clone(),
+            lmis: self.lmis.clone(),
+            var_names: self.var_names.clone(),
+            constr_names: self.constr_names.clone(),
+        })
+    }
+
+    /// Create a model and load a whole problem into it, see `load_csc`.
+    pub fn from_csc(env: &Env, prob: &CscProblem) -> crate::error::Result<Model> {
+        let mut model = Model::new(env)?;
+        model.load_csc(prob)?;
         Ok(model)
     }
 
+    /// Replace the problem with one given in compressed sparse column form, using a single
+    /// `COPT_LoadProb` call.
+    ///
+    /// Handles created before the call refer to removed objects afterwards.
+    pub fn load_csc(&mut self, prob: &CscProblem) -> crate::error::Result<()> {
+        prob.check_dims()?;
+        let (ncols, nrows) = (prob.obj.len(), prob.senses.len());
+        let col_cnt: Vec<_> = prob.col_ptr.windows(2).map(|w| w[1] - w[0]).collect();
+        let vtypes: Vec<std::ffi::c_char> = prob.vtypes.iter().map(|&vtype| vtype.into()).collect();
+        let senses: Vec<std::ffi::c_char> = prob.senses.iter().map(|&sense| sense.into()).collect();
+        let uppers = vec![0.0; nrows];
+        let col_names = prob.col_names.iter().map(|name| CString::new(name.as_str()));
+        let col_names = col_names.collect::<Result<Vec<_>, _>>()?;
+        let row_names = prob.row_names.iter().map(|name| CString::new(name.as_str()));
+        let row_names = row_names.collect::<Result<Vec<_>, _>>()?;
+        let col_name_ptrs: Vec<_> = col_names.iter().map(|name| name.as_ptr()).collect();
+        let row_name_ptrs: Vec<_> = row_names.iter().map(|name| name.as_ptr()).collect();
+        self.check_apicall(unsafe {
+            copt_sys::COPT_LoadProb(
+                self.model,
+                ncols as std::ffi::c_int,
+                nrows as std::ffi::c_int,
+                prob.sense.into(),
+                prob.obj_const,
+                prob.obj.as_ptr(),
+                prob.col_ptr.as_ptr(),
+                col_cnt.as_ptr(),
+                prob.row_ind.as_ptr(),
+                prob.values.as_ptr(),
+                ptr_or_null(&vtypes),
+                ptr_or_null(&prob.lbs),
+                ptr_or_null(&prob.ubs),
+                senses.as_ptr(),
+                prob.rhs.as_ptr(),
+                uppers.as_ptr(),
+                ptr_or_null(&col_name_ptrs),
+                ptr_or_null(&row_name_ptrs),
+            )
+        })?;
+        self.sync_handles()
+    }
+
     pub fn add_var(
         &mut self,
         name: &str,
@@ -156,91 +638,1021 @@ impl Model {
         lb: f64,
         ub: f64,
         constrs: &[Constr],
-        coeffs: &[f64],
-    ) -> crate::error::Result<Var> {
-        if constrs.len() != coeffs.len() {
+        coeffs: &[f64],
+    ) -> crate::error::Result<Var> {
+        if constrs.len() != coeffs.len() {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        let constrs = self.constr_indices(constrs)?;
+        let name_c = CString::new(name)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddCol(
+                self.model,
+                obj,
+                constrs.len() as std::ffi::c_int,
+                constrs.as_ptr(),
+                coeffs.as_ptr(),
+                vtype.into(),
+                lb,
+                ub,
+                name_c.as_ptr(),
+            )
+        })?;
+        Ok(self.new_var())
+    }
+
+    /// Add several variables with a single `COPT_AddCols` call.
+    ///
+    /// Each item is `(name, vtype, obj, lb, ub, constrs, coeffs)`, where `constrs` and `coeffs`
+    /// are the (possibly empty) column entries of the new variable.
+    pub fn add_vars<'a, I>(&mut self, vars: I) -> crate::error::Result<Vec<Var>>
+    where
+        I: IntoIterator<Item = (&'a str, VarType, f64, f64, f64, &'a [Constr], &'a [f64])>,
+    {
+        let mut names = Vec::new();
+        let mut vtypes = Vec::new();
+        let mut objs = Vec::new();
+        let mut lbs = Vec::new();
+        let mut ubs = Vec::new();
+        let mut beg = Vec::new();
+        let mut cnt = Vec::new();
+        let mut ind = Vec::new();
+        let mut val = Vec::new();
+        for (name, vtype, obj, lb, ub, constrs, coeffs) in vars {
+            if constrs.len() != coeffs.len() {
+                return Err(crate::error::Error::InconsitentDims);
+            }
+            names.push(CString::new(name)?);
+            vtypes.push(vtype.into());
+            objs.push(obj);
+            lbs.push(lb);
+            ubs.push(ub);
+            beg.push(ind.len() as std::ffi::c_int);
+            cnt.push(constrs.len() as std::ffi::c_int);
+            ind.extend(self.constr_indices(constrs)?);
+            val.extend_from_slice(coeffs);
+        }
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let name_ptrs: Vec<_> = names.iter().map(|name| name.as_ptr()).collect();
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddCols(
+                self.model,
+                names.len() as std::ffi::c_int,
+                objs.as_ptr(),
+                beg.as_ptr(),
+                cnt.as_ptr(),
+                ind.as_ptr(),
+                val.as_ptr(),
+                vtypes.as_ptr(),
+                lbs.as_ptr(),
+                ubs.as_ptr(),
+                name_ptrs.as_ptr(),
+            )
+        })?;
+        Ok((0..names.len()).map(|_| self.new_var()).collect())
+    }
+
+    pub fn add_constr_low_level(
+        &mut self,
+        name: &str,
+        vars: &[Var],
+        coeffs: &[f64],
+        sense: ConstrSense,
+        rhs: f64,
+    ) -> crate::error::Result<Constr> {
+        if vars.len() != coeffs.len() {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        let vars = self.var_indices(vars)?;
+        let constrname = CString::new(name)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddRow(
+                self.model,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                sense.into(),
+                rhs,
+                0.0,
+                constrname.as_ptr(),
+            )
+        })?;
+        Ok(self.new_constr())
+    }
+
+    pub fn add_constr(
+        &mut self,
+        name: &str,
+        expr: crate::expr::LinExpr,
+        sense: ConstrSense,
+        rhs: f64,
+    ) -> crate::error::Result<Constr> {
+        let (vars, coeffs, offset) = expr.into();
+        let vars = self.var_indices(&vars)?;
+        let constrname = CString::new(name)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddRow(
+                self.model,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                sense.into(),
+                rhs - offset,
+                0.0,
+                constrname.as_ptr(),
+            )
+        })?;
+        Ok(self.new_constr())
+    }
+
+    /// Add a range constraint `lb <= expr <= ub` as a single row.
+    pub fn add_range(
+        &mut self,
+        name: &str,
+        expr: crate::expr::LinExpr,
+        lb: f64,
+        ub: f64,
+    ) -> crate::error::Result<Constr> {
+        if lb > ub {
+            return Err(crate::error::Error::InvalidArgument);
+        }
+        let (vars, coeffs, offset) = expr.into();
+        let vars = self.var_indices(&vars)?;
+        let constrname = CString::new(name)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddRow(
+                self.model,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                BOUNDS_SENSE,
+                lb - offset,
+                ub - offset,
+                constrname.as_ptr(),
+            )
+        })?;
+        Ok(self.new_constr())
+    }
+
+    /// Add a lazy constraint `expr sense rhs`, which the MIP solver only enforces once a
+    /// solution candidate violates it.
+    pub fn add_lazy_constr(
+        &mut self,
+        name: &str,
+        expr: crate::expr::LinExpr,
+        sense: ConstrSense,
+        rhs: f64,
+    ) -> crate::error::Result<()> {
+        let (vars, coeffs, offset) = expr.into();
+        let vars = self.var_indices(&vars)?;
+        let constrname = CString::new(name)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddLazyConstr(
+                self.model,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                sense.into(),
+                rhs - offset,
+                0.0,
+                constrname.as_ptr(),
+            )
+        })
+    }
+
+    /// Add several linear constraints with a single `COPT_AddRows` call.
+    ///
+    /// Each item is `(name, expr, sense, rhs)`. The returned handles are in the same order as the
+    /// input.
+    pub fn add_constrs<'a, I>(&mut self, constrs: I) -> crate::error::Result<Vec<Constr>>
+    where
+        I: IntoIterator<Item = (&'a str, crate::expr::LinExpr, ConstrSense, f64)>,
+    {
+        let mut names = Vec::new();
+        let mut senses = Vec::new();
+        let mut rhss = Vec::new();
+        let mut beg = Vec::new();
+        let mut cnt = Vec::new();
+        let mut ind = Vec::new();
+        let mut val = Vec::new();
+        for (name, expr, sense, rhs) in constrs {
+            let (vars, coeffs, offset) = expr.into();
+            let vars = self.var_indices(&vars)?;
+            names.push(CString::new(name)?);
+            senses.push(sense.into());
+            rhss.push(rhs - offset);
+            beg.push(ind.len() as std::ffi::c_int);
+            cnt.push(vars.len() as std::ffi::c_int);
+            ind.extend(vars);
+            val.extend(coeffs);
+        }
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let uppers = vec![0.0; names.len()];
+        let name_ptrs: Vec<_> = names.iter().map(|name| name.as_ptr()).collect();
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddRows(
+                self.model,
+                names.len() as std::ffi::c_int,
+                beg.as_ptr(),
+                cnt.as_ptr(),
+                ind.as_ptr(),
+                val.as_ptr(),
+                senses.as_ptr(),
+                rhss.as_ptr(),
+                uppers.as_ptr(),
+                name_ptrs.as_ptr(),
+            )
+        })?;
+        Ok((0..names.len()).map(|_| self.new_constr()).collect())
+    }
+
+    /// Remove variables from the model.
+    ///
+    /// Handles of the remaining variables stay valid; handles of the removed ones return
+    /// `Error::RemovedHandle` from then on.
+    pub fn remove_vars(&mut self, vars: &[Var]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.vars, vars)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelCols(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.vars.remove(&list);
+        self.var_names.get_mut().retain(|_, serial| self.vars.index(*serial).is_some());
+        Ok(())
+    }
+
+    /// Remove linear constraints from the model.
+    ///
+    /// Handles of the remaining constraints stay valid; handles of the removed ones return
+    /// `Error::RemovedHandle` from then on.
+    pub fn remove_constrs(&mut self, constrs: &[Constr]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.constrs, constrs)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelRows(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.constrs.remove(&list);
+        self.constr_names.get_mut().retain(|_, serial| self.constrs.index(*serial).is_some());
+        Ok(())
+    }
+
+    /// Get the linear expression of a constraint row.
+    pub fn get_row(&self, constr: Constr) -> crate::error::Result<crate::expr::LinExpr> {
+        Ok(self.get_rows(&[constr])?.pop().unwrap())
+    }
+
+    /// Get the linear expressions of several constraint rows.
+    pub fn get_rows(&self, constrs: &[Constr]) -> crate::error::Result<Vec<crate::expr::LinExpr>> {
+        let list = self.constr_indices(constrs)?;
+        let rows = self.get_matrix(&list, copt_sys::COPT_GetRows)?;
+        Ok((0..list.len())
+            .map(|k| {
+                let (ind, val) = rows.entries(k);
+                let vars: Vec<_> = ind.iter().map(|&col| self.var_at(col)).collect();
+                crate::expr::LinExpr::new().add_terms(val, &vars)
+            })
+            .collect())
+    }
+
+    /// Get the `(constr, coeff)` entries of a variable column.
+    pub fn get_col(&self, var: Var) -> crate::error::Result<Vec<(Constr, f64)>> {
+        Ok(self.get_cols(&[var])?.pop().unwrap())
+    }
+
+    /// Get the `(constr, coeff)` entries of several variable columns.
+    pub fn get_cols(&self, vars: &[Var]) -> crate::error::Result<Vec<Vec<(Constr, f64)>>> {
+        let list = self.var_indices(vars)?;
+        let cols = self.get_matrix(&list, copt_sys::COPT_GetCols)?;
+        Ok((0..list.len())
+            .map(|k| {
+                let (ind, val) = cols.entries(k);
+                ind.iter().map(|&row| self.constr_at(row)).zip(val.iter().copied()).collect()
+            })
+            .collect())
+    }
+
+    /// Add a quadratic constraint `expr sense rhs`.
+    pub fn add_qconstr(
+        &mut self,
+        name: &str,
+        expr: crate::expr::QuadExpr,
+        sense: ConstrSense,
+        rhs: f64,
+    ) -> crate::error::Result<QConstr> {
+        let (rows, cols, qcoeffs, lin) = expr.into();
+        let rows = self.var_indices(&rows)?;
+        let cols = self.var_indices(&cols)?;
+        let (vars, coeffs, offset) = lin.into();
+        let vars = self.var_indices(&vars)?;
+        let constrname = CString::new(name)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddQConstr(
+                self.model,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                qcoeffs.len() as std::ffi::c_int,
+                rows.as_ptr(),
+                cols.as_ptr(),
+                qcoeffs.as_ptr(),
+                sense.into(),
+                rhs - offset,
+                constrname.as_ptr(),
+            )
+        })?;
+        Ok(QConstr::new(self.id, self.qconstrs.push()))
+    }
+
+    /// Get the expression, sense and right-hand side of a quadratic constraint.
+    pub fn get_qconstr(
+        &self,
+        qconstr: QConstr,
+    ) -> crate::error::Result<(crate::expr::QuadExpr, ConstrSense, f64)> {
+        let index = self.index_in(&self.qconstrs, qconstr)?;
+        let (mut qsize, mut size) = (0, 0);
+        let mut sense: std::ffi::c_char = 0;
+        let mut rhs = 0.0;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_GetQConstr(
+                self.model,
+                index,
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                0,
+                &mut qsize,
+                null_mut(),
+                null_mut(),
+                &mut sense,
+                &mut rhs,
+                0,
+                &mut size,
+            )
+        })?;
+        let mut rows = vec![0; qsize as usize];
+        let mut cols = vec![0; qsize as usize];
+        let mut qcoeffs = vec![0.0; qsize as usize];
+        let mut vars = vec![0; size as usize];
+        let mut coeffs = vec![0.0; size as usize];
+        self.check_apicall(unsafe {
+            copt_sys::COPT_GetQConstr(
+                self.model,
+                index,
+                rows.as_mut_ptr(),
+                cols.as_mut_ptr(),
+                qcoeffs.as_mut_ptr(),
+                qsize,
+                null_mut(),
+                vars.as_mut_ptr(),
+                coeffs.as_mut_ptr(),
+                &mut sense,
+                &mut rhs,
+                size,
+                null_mut(),
+            )
+        })?;
+        let sense = ConstrSense::from_raw(sense).ok_or(crate::error::Error::UnknownSense)?;
+        let rows: Vec<_> = rows.iter().map(|&row| self.var_at(row)).collect();
+        let cols: Vec<_> = cols.iter().map(|&col| self.var_at(col)).collect();
+        let vars: Vec<_> = vars.iter().map(|&col| self.var_at(col)).collect();
+        let lin = crate::expr::LinExpr::new().add_terms(&coeffs, &vars);
+        Ok((crate::expr::QuadExpr::new().add_terms(&qcoeffs, &rows, &cols) + lin, sense, rhs))
+    }
+
+    /// Remove quadratic constraints from the model.
+    pub fn remove_qconstrs(&mut self, qconstrs: &[QConstr]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.qconstrs, qconstrs)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelQConstrs(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.qconstrs.remove(&list);
+        Ok(())
+    }
+
+    /// Get the slack values of quadratic constraints after solving.
+    pub fn get_qconstrs_slack(&self, qconstrs: &[QConstr]) -> crate::error::Result<Vec<f64>> {
+        let list = self.indices_in(&self.qconstrs, qconstrs)?;
+        self.get_info(copt_sys::COPT_GetQConstrInfo, "Slack", &list)
+    }
+
+    /// Get the dual values of quadratic constraints after solving.
+    pub fn get_qconstrs_dual(&self, qconstrs: &[QConstr]) -> crate::error::Result<Vec<f64>> {
+        let list = self.indices_in(&self.qconstrs, qconstrs)?;
+        self.get_info(copt_sys::COPT_GetQConstrInfo, "Dual", &list)
+    }
+
+    /// Add an SOS constraint over `vars`, ordered by the given distinct `weights`.
+    ///
+    /// Fails with `Error::InvalidArgument` if `vars` is empty or the weights are not finite and
+    /// distinct.
+    pub fn add_sos(
+        &mut self,
+        sostype: SosType,
+        vars: &[Var],
+        weights: &[f64],
+    ) -> crate::error::Result<Sos> {
+        if vars.len() != weights.len() {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        let mut sorted = weights.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        if vars.is_empty()
+            || sorted.iter().any(|weight| !weight.is_finite())
+            || sorted.windows(2).any(|w| w[0] == w[1])
+        {
+            return Err(crate::error::Error::InvalidArgument);
+        }
+        let vars = self.var_indices(vars)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddSOSs(
+                self.model,
+                1,
+                &(sostype as std::ffi::c_int),
+                &0,
+                &(vars.len() as std::ffi::c_int),
+                vars.as_ptr(),
+                weights.as_ptr(),
+            )
+        })?;
+        Ok(Sos::new(self.id, self.soss.push()))
+    }
+
+    /// Remove SOS constraints from the model.
+    pub fn remove_soss(&mut self, soss: &[Sos]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.soss, soss)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelSOSs(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.soss.remove(&list);
+        Ok(())
+    }
+
+    /// Add an indicator constraint: if `binvar` equals `binval`, then `expr sense rhs` must hold.
+    pub fn add_indicator(
+        &mut self,
+        binvar: Var,
+        binval: bool,
+        expr: crate::expr::LinExpr,
+        sense: ConstrSense,
+        rhs: f64,
+    ) -> crate::error::Result<Indicator> {
+        let bincol = self.var_index(binvar)?;
+        let (vars, coeffs, offset) = expr.into();
+        let vars = self.var_indices(&vars)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddIndicator(
+                self.model,
+                bincol,
+                binval as std::ffi::c_int,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                sense.into(),
+                rhs - offset,
+            )
+        })?;
+        Ok(Indicator::new(self.id, self.indicators.push()))
+    }
+
+    /// Remove indicator constraints from the model.
+    pub fn remove_indicators(&mut self, indicators: &[Indicator]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.indicators, indicators)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelIndicators(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.indicators.remove(&list);
+        Ok(())
+    }
+
+    /// Add a second-order cone constraint over `vars`.
+    ///
+    /// Cone constraints are not covered by COPT's IIS computation, so they never show up in IIS
+    /// results; this crate has no IIS API either.
+    pub fn add_cone(&mut self, conetype: ConeType, vars: &[Var]) -> crate::error::Result<Cone> {
+        let min_len = match conetype {
+            ConeType::Quad => 2,
+            ConeType::RotatedQuad => 3,
+        };
+        if vars.len() < min_len {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        let cols = self.var_indices(vars)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddCones(
+                self.model,
+                1,
+                &(conetype as std::ffi::c_int),
+                &0,
+                &(cols.len() as std::ffi::c_int),
+                cols.as_ptr(),
+            )
+        })?;
+        let cone = Cone::new(self.id, self.cones.push());
+        self.cone_vars.insert(cone.serial, vars.to_vec());
+        Ok(cone)
+    }
+
+    /// Remove second-order cone constraints from the model.
+    pub fn remove_cones(&mut self, cones: &[Cone]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.cones, cones)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelCones(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.cones.remove(&list);
+        for cone in cones {
+            self.cone_vars.remove(&cone.serial);
+        }
+        Ok(())
+    }
+
+    /// Add an exponential cone constraint over three variables.
+    pub fn add_exp_cone(
+        &mut self,
+        conetype: ExpConeType,
+        vars: [Var; 3],
+    ) -> crate::error::Result<ExpCone> {
+        let cols = self.var_indices(&vars)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddExpCones(self.model, 1, &(conetype as std::ffi::c_int), cols.as_ptr())
+        })?;
+        let cone = ExpCone::new(self.id, self.exp_cones.push());
+        self.exp_cone_vars.insert(cone.serial, vars);
+        Ok(cone)
+    }
+
+    /// Remove exponential cone constraints from the model.
+    pub fn remove_exp_cones(&mut self, cones: &[ExpCone]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.exp_cones, cones)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelExpCones(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.exp_cones.remove(&list);
+        for cone in cones {
+            self.exp_cone_vars.remove(&cone.serial);
+        }
+        Ok(())
+    }
+
+    /// Get the dual values of an exponential cone after an optimal solve.
+    ///
+    /// These are read from the reduced costs of the cone variables, which equal the cone dual
+    /// only if nothing else adds dual terms to those variables. This fails with
+    /// `Error::ConeDualUnavailable` if any of the three variables is in another cone or within
+    /// `FeasTol` of a finite bound, if the model has quadratic, PSD or LMI constraints or a
+    /// quadratic objective, or if it holds cones that were not added through `add_cone` or
+    /// `add_exp_cone`.
+    pub fn get_exp_cone_dual(&self, cone: ExpCone) -> crate::error::Result<[f64; 3]> {
+        self.index_in(&self.exp_cones, cone)?;
+        let vars =
+            self.exp_cone_vars.get(&cone.serial).ok_or(crate::error::Error::RemovedHandle)?;
+        let list = self.var_indices(vars)?;
+        let coupled = self.get_attribute(IntAttr::QConstrs)? > 0
+            || self.get_attribute(IntAttr::PSDConstrs)? > 0
+            || self.get_attribute(IntAttr::LMIConstrs)? > 0
+            || self.get_attribute(IntAttr::HasQObj)? != 0;
+        let untracked = self.cone_vars.len() != self.cones.len()
+            || self.exp_cone_vars.len() != self.exp_cones.len();
+        let other_exp_cones =
+            self.exp_cone_vars.iter().filter(|&(&serial, _)| serial != cone.serial);
+        let shared = self
+            .cone_vars
+            .values()
+            .flatten()
+            .chain(other_exp_cones.flat_map(|(_, other)| other))
+            .any(|var| vars.contains(var));
+        let values = self.get_info(copt_sys::COPT_GetColInfo, "Value", &list)?;
+        let lbs = self.get_info(copt_sys::COPT_GetColInfo, "LB", &list)?;
+        let ubs = self.get_info(copt_sys::COPT_GetColInfo, "UB", &list)?;
+        let tol = self.get_param(copt_sys::DoubleParam::FeasTol)?;
+        let at_bound = (0..3).any(|k| {
+            (lbs[k] > -crate::INFINITY && values[k] - lbs[k] <= tol)
+                || (ubs[k] < crate::INFINITY && ubs[k] - values[k] <= tol)
+        });
+        if coupled || untracked || shared || at_bound {
+            return Err(crate::error::Error::ConeDualUnavailable);
+        }
+        let duals = self.get_info(copt_sys::COPT_GetColInfo, "RedCost", &list)?;
+        Ok([duals[0], duals[1], duals[2]])
+    }
+
+    /// Add a positive semidefinite matrix variable of size `dim` x `dim`.
+    pub fn add_psd_var(&mut self, dim: i32, name: &str) -> crate::error::Result<PsdVar> {
+        let name_c = CString::new(name)?;
+        self.check_apicall(unsafe { copt_sys::COPT_AddPSDCol(self.model, dim, name_c.as_ptr()) })?;
+        Ok(PsdVar::new(self.id, self.psd_vars.push()))
+    }
+
+    /// Remove PSD variables from the model.
+    pub fn remove_psd_vars(&mut self, psd_vars: &[PsdVar]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.psd_vars, psd_vars)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelPSDCols(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.psd_vars.remove(&list);
+        Ok(())
+    }
+
+    /// Register a symmetric `dim` x `dim` matrix given by its lower-triangular entries
+    /// (`rows[k] >= cols[k]`).
+    pub fn add_sym_mat(
+        &mut self,
+        dim: i32,
+        rows: &[i32],
+        cols: &[i32],
+        vals: &[f64],
+    ) -> crate::error::Result<SymMat> {
+        if rows.len() != vals.len() || cols.len() != vals.len() {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddSymMat(
+                self.model,
+                dim,
+                vals.len() as std::ffi::c_int,
+                rows.as_ptr(),
+                cols.as_ptr(),
+                vals.as_ptr(),
+            )
+        })?;
+        Ok(SymMat::new(self.id, self.sym_mats.push()))
+    }
+
+    /// Add a constraint `expr sense rhs` over PSD and scalar variables.
+    pub fn add_psd_constr(
+        &mut self,
+        name: &str,
+        expr: crate::expr::PsdExpr,
+        sense: ConstrSense,
+        rhs: f64,
+    ) -> crate::error::Result<PsdConstr> {
+        let (psd_vars, mats, lin) = expr.into();
+        let psd_cols = self.indices_in(&self.psd_vars, &psd_vars)?;
+        let mats = self.indices_in(&self.sym_mats, &mats)?;
+        let (vars, coeffs, offset) = lin.into();
+        let vars = self.var_indices(&vars)?;
+        let constrname = CString::new(name)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddPSDConstr(
+                self.model,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                coeffs.as_ptr(),
+                psd_cols.len() as std::ffi::c_int,
+                psd_cols.as_ptr(),
+                mats.as_ptr(),
+                sense.into(),
+                rhs - offset,
+                0.0,
+                constrname.as_ptr(),
+            )
+        })?;
+        Ok(PsdConstr::new(self.id, self.psd_constrs.push()))
+    }
+
+    /// Remove PSD constraints from the model.
+    pub fn remove_psd_constrs(&mut self, psd_constrs: &[PsdConstr]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.psd_constrs, psd_constrs)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelPSDConstrs(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.psd_constrs.remove(&list);
+        Ok(())
+    }
+
+    /// Set an objective over PSD and scalar variables, replacing the previous one.
+    ///
+    /// COPT takes a single objective matrix per PSD variable, so `expr` may contain each PSD
+    /// variable only once; otherwise this fails with `Error::InvalidArgument`.
+    pub fn set_psd_objective(
+        &mut self,
+        expr: crate::expr::PsdExpr,
+        sense: ModelSense,
+    ) -> crate::error::Result<()> {
+        let (psd_vars, mats, lin) = expr.into();
+        let psd_cols = self.indices_in(&self.psd_vars, &psd_vars)?;
+        let mats = self.indices_in(&self.sym_mats, &mats)?;
+        let mut sorted = psd_cols.clone();
+        sorted.sort_unstable();
+        if sorted.windows(2).any(|w| w[0] == w[1]) {
+            return Err(crate::error::Error::InvalidArgument);
+        }
+        self.set_objective(lin, sense)?;
+        self.check_apicall(unsafe { copt_sys::COPT_DelPSDObj(self.model) })?;
+        for (psd_col, mat) in psd_cols.into_iter().zip(mats) {
+            self.check_apicall(unsafe { copt_sys::COPT_SetPSDObj(self.model, psd_col, mat) })?;
+        }
+        Ok(())
+    }
+
+    /// Get the value of a PSD variable after solving, as its lower triangle packed column by
+    /// column.
+    pub fn get_psd_var_value(&self, psd_var: PsdVar) -> crate::error::Result<Vec<f64>> {
+        let index = self.index_in(&self.psd_vars, psd_var)? as usize;
+        let solution = self.get_psd_solution()?;
+        Ok(solution.values[solution.offsets[index]..solution.offsets[index + 1]].to_vec())
+    }
+
+    /// Get the dual matrix of a PSD variable after solving, packed like `get_psd_var_value`.
+    pub fn get_psd_var_dual(&self, psd_var: PsdVar) -> crate::error::Result<Vec<f64>> {
+        let index = self.index_in(&self.psd_vars, psd_var)? as usize;
+        let solution = self.get_psd_solution()?;
+        Ok(solution.col_duals[solution.offsets[index]..solution.offsets[index + 1]].to_vec())
+    }
+
+    /// Get the slack values of PSD constraints after solving.
+    pub fn get_psd_constrs_slack(
+        &self,
+        psd_constrs: &[PsdConstr],
+    ) -> crate::error::Result<Vec<f64>> {
+        let list = self.indices_in(&self.psd_constrs, psd_constrs)?;
+        let solution = self.get_psd_solution()?;
+        Ok(list.iter().map(|&index| solution.row_slacks[index as usize]).collect())
+    }
+
+    /// Get the dual values of PSD constraints after solving.
+    pub fn get_psd_constrs_dual(
+        &self,
+        psd_constrs: &[PsdConstr],
+    ) -> crate::error::Result<Vec<f64>> {
+        let list = self.indices_in(&self.psd_constrs, psd_constrs)?;
+        let solution = self.get_psd_solution()?;
+        Ok(list.iter().map(|&index| solution.row_duals[index as usize]).collect())
+    }
+
+    /// Add a linear matrix inequality stating that the `dim` x `dim` matrix `expr` is positive
+    /// semidefinite.
+    pub fn add_lmi(
+        &mut self,
+        name: &str,
+        dim: i32,
+        expr: crate::expr::LmiExpr,
+    ) -> crate::error::Result<Lmi> {
+        let (vars, mats, constant) = expr.into();
+        let vars = self.var_indices(&vars)?;
+        let mats = self.indices_in(&self.sym_mats, &mats)?;
+        let constant = match constant {
+            Some(mat) => self.index_in(&self.sym_mats, mat)?,
+            None => -1,
+        };
+        let constrname = CString::new(name)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_AddLMIConstr(
+                self.model,
+                dim,
+                vars.len() as std::ffi::c_int,
+                vars.as_ptr(),
+                mats.as_ptr(),
+                constant,
+                constrname.as_ptr(),
+            )
+        })?;
+        Ok(Lmi::new(self.id, self.lmis.push()))
+    }
+
+    /// Remove linear matrix inequalities from the model.
+    pub fn remove_lmis(&mut self, lmis: &[Lmi]) -> crate::error::Result<()> {
+        let list = self.removal_list(&self.lmis, lmis)?;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_DelLMIConstrs(self.model, list.len() as std::ffi::c_int, list.as_ptr())
+        })?;
+        self.lmis.remove(&list);
+        Ok(())
+    }
+
+    /// Get the slack matrix of a linear matrix inequality after solving, as its lower triangle
+    /// packed column by column.
+    pub fn get_lmi_slack(&self, lmi: Lmi) -> crate::error::Result<Vec<f64>> {
+        let (slacks, _, range) = self.get_lmi_solution(lmi)?;
+        Ok(slacks[range].to_vec())
+    }
+
+    /// Get the dual matrix of a linear matrix inequality after solving, packed like
+    /// `get_lmi_slack`.
+    pub fn get_lmi_dual(&self, lmi: Lmi) -> crate::error::Result<Vec<f64>> {
+        let (_, duals, range) = self.get_lmi_solution(lmi)?;
+        Ok(duals[range].to_vec())
+    }
+
+    /// Set the lower bound of a variable.
+    pub fn set_var_lb(&mut self, var: Var, lb: f64) -> crate::error::Result<()> {
+        self.set_vars_lb(&[var], &[lb])
+    }
+
+    /// Set the lower bounds of several variables.
+    pub fn set_vars_lb(&mut self, vars: &[Var], lbs: &[f64]) -> crate::error::Result<()> {
+        self.set_col_values(vars, lbs, copt_sys::COPT_SetColLower)
+    }
+
+    /// Set the upper bound of a variable.
+    pub fn set_var_ub(&mut self, var: Var, ub: f64) -> crate::error::Result<()> {
+        self.set_vars_ub(&[var], &[ub])
+    }
+
+    /// Set the upper bounds of several variables.
+    pub fn set_vars_ub(&mut self, vars: &[Var], ubs: &[f64]) -> crate::error::Result<()> {
+        self.set_col_values(vars, ubs, copt_sys::COPT_SetColUpper)
+    }
+
+    /// Set the objective coefficient of a variable.
+    pub fn set_var_obj(&mut self, var: Var, obj: f64) -> crate::error::Result<()> {
+        self.set_vars_obj(&[var], &[obj])
+    }
+
+    /// Set the objective coefficients of several variables.
+    pub fn set_vars_obj(&mut self, vars: &[Var], objs: &[f64]) -> crate::error::Result<()> {
+        self.set_col_values(vars, objs, copt_sys::COPT_SetColObj)
+    }
+
+    /// Set the type of a variable.
+    pub fn set_var_type(&mut self, var: Var, vtype: VarType) -> crate::error::Result<()> {
+        self.set_vars_type(&[var], &[vtype])
+    }
+
+    /// Set the types of several variables.
+    pub fn set_vars_type(&mut self, vars: &[Var], vtypes: &[VarType]) -> crate::error::Result<()> {
+        let vtypes: Vec<std::ffi::c_char> = vtypes.iter().map(|&vtype| vtype.into()).collect();
+        self.set_col_values(vars, &vtypes, copt_sys::COPT_SetColType)
+    }
+
+    /// Set the name of a variable.
+    pub fn set_var_name(&mut self, var: Var, name: &str) -> crate::error::Result<()> {
+        self.set_vars_name(&[var], &[name])
+    }
+
+    /// Set the names of several variables.
+    pub fn set_vars_name(&mut self, vars: &[Var], names: &[&str]) -> crate::error::Result<()> {
+        let names = names.iter().map(|&name| CString::new(name)).collect::<Result<Vec<_>, _>>()?;
+        let name_ptrs: Vec<_> = names.iter().map(|name| name.as_ptr()).collect();
+        self.set_col_values(vars, &name_ptrs, copt_sys::COPT_SetColNames)?;
+        self.var_names.get_mut().clear();
+        Ok(())
+    }
+
+    /// Get the lower and upper bounds of a constraint.
+    pub fn get_constr_bounds(&self, constr: Constr) -> crate::error::Result<(f64, f64)> {
+        let list = [self.constr_index(constr)?];
+        let lb = self.get_info(copt_sys::COPT_GetRowInfo, "LB", &list)?[0];
+        let ub = self.get_info(copt_sys::COPT_GetRowInfo, "UB", &list)?[0];
+        Ok((lb, ub))
+    }
+
+    /// Set the lower and upper bounds of a constraint.
+    pub fn set_constr_bounds(
+        &mut self,
+        constr: Constr,
+        lb: f64,
+        ub: f64,
+    ) -> crate::error::Result<()> {
+        self.set_constrs_bounds(&[constr], &[lb], &[ub])
+    }
+
+    /// Set the lower and upper bounds of several constraints.
+    pub fn set_constrs_bounds(
+        &mut self,
+        constrs: &[Constr],
+        lbs: &[f64],
+        ubs: &[f64],
+    ) -> crate::error::Result<()> {
+        if constrs.len() != lbs.len() || constrs.len() != ubs.len() {
             return Err(crate::error::Error::InconsitentDims);
         }
-        let name_c = CString::new(name)?;
+        let list = self.constr_indices(constrs)?;
+        let num = list.len() as std::ffi::c_int;
         self.check_apicall(unsafe {
-            copt_sys::COPT_AddCol(
-                self.model,
-                obj,
-                constrs.len() as std::ffi::c_int,
-                constrs.as_ptr(),
-                coeffs.as_ptr(),
-                vtype.into(),
-                lb,
-                ub,
-                name_c.as_ptr(),
-            )
+            copt_sys::COPT_SetRowLower(self.model, num, list.as_ptr(), lbs.as_ptr())
         })?;
-        self.var_count += 1;
-        Ok(Var(self.var_count - 1))
+        self.check_apicall(unsafe {
+            copt_sys::COPT_SetRowUpper(self.model, num, list.as_ptr(), ubs.as_ptr())
+        })
     }
 
-    pub fn add_constr_low_level(
+    /// Change the right-hand side of a constraint, keeping its sense.
+    ///
+    /// Range constraints have no single right-hand side; use `set_constr_bounds` for them.
+    pub fn set_constr_rhs(&mut self, constr: Constr, rhs: f64) -> crate::error::Result<()> {
+        self.set_constrs_rhs(&[constr], &[rhs])
+    }
+
+    /// Change the right-hand sides of several constraints, keeping their senses.
+    ///
+    /// Fails with `Error::RangeConstr` if any of them is a range constraint.
+    pub fn set_constrs_rhs(
         &mut self,
-        name: &str,
-        vars: &[i32],
-        coeffs: &[f64],
-        sense: ConstrSense,
-        rhs: f64,
-    ) -> crate::error::Result<Constr> {
-        if vars.len() != coeffs.len() {
+        constrs: &[Constr],
+        rhss: &[f64],
+    ) -> crate::error::Result<()> {
+        if constrs.len() != rhss.len() {
             return Err(crate::error::Error::InconsitentDims);
         }
-        let constrname = CString::new(name)?;
-        self.check_apicall(unsafe {
-            copt_sys::COPT_AddRow(
-                self.model,
-                vars.len() as std::ffi::c_int,
-                vars.as_ptr(),
-                coeffs.as_ptr(),
-                sense.into(),
-                rhs,
-                0.0,
-                constrname.as_ptr(),
-            )
-        })?;
-        self.constr_count += 1;
-        Ok(self.constr_count - 1)
+        let list = self.constr_indices(constrs)?;
+        let mut lbs = self.get_info(copt_sys::COPT_GetRowInfo, "LB", &list)?;
+        let mut ubs = self.get_info(copt_sys::COPT_GetRowInfo, "UB", &list)?;
+        for ((lb, ub), &rhs) in lbs.iter_mut().zip(ubs.iter_mut()).zip(rhss) {
+            if *lb == *ub {
+                (*lb, *ub) = (rhs, rhs);
+            } else if *lb <= -crate::INFINITY {
+                *ub = rhs;
+            } else if *ub >= crate::INFINITY {
+                *lb = rhs;
+            } else {
+                return Err(crate::error::Error::RangeConstr);
+            }
+        }
+        self.set_constrs_bounds(constrs, &lbs, &ubs)
     }
 
-    pub fn add_constr(
+    /// Change the sense and right-hand side of a constraint.
+    pub fn set_constr_sense(
         &mut self,
-        name: &str,
-        expr: crate::expr::LinExpr,
+        constr: Constr,
         sense: ConstrSense,
         rhs: f64,
-    ) -> crate::error::Result<Constr> {
-        let (vars, coeffs, offset) = expr.into();
-        let constrname = CString::new(name)?;
-        self.check_apicall(unsafe {
-            copt_sys::COPT_AddRow(
-                self.model,
-                vars.len() as std::ffi::c_int,
-                vars.as_ptr(),
-                coeffs.as_ptr(),
-                sense.into(),
-                rhs - offset,
-                0.0,
-                constrname.as_ptr(),
-            )
-        })?;
-        self.constr_count += 1;
-        Ok(self.constr_count - 1)
+    ) -> crate::error::Result<()> {
+        self.set_constrs_sense(&[constr], &[sense], &[rhs])
+    }
+
+    /// Change the senses and right-hand sides of several constraints.
+    pub fn set_constrs_sense(
+        &mut self,
+        constrs: &[Constr],
+        senses: &[ConstrSense],
+        rhss: &[f64],
+    ) -> crate::error::Result<()> {
+        if senses.len() != rhss.len() {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        let (lbs, ubs): (Vec<f64>, Vec<f64>) = senses
+            .iter()
+            .zip(rhss)
+            .map(|(sense, &rhs)| match sense {
+                ConstrSense::Equal => (rhs, rhs),
+                ConstrSense::Greater => (rhs, crate::INFINITY),
+                ConstrSense::Less => (-crate::INFINITY, rhs),
+            })
+            .unzip();
+        self.set_constrs_bounds(constrs, &lbs, &ubs)
+    }
+
+    /// Set the name of a constraint.
+    pub fn set_constr_name(&mut self, constr: Constr, name: &str) -> crate::error::Result<()> {
+        self.set_constrs_name(&[constr], &[name])
+    }
+
+    /// Set the names of several constraints.
+    pub fn set_constrs_name(
+        &mut self,
+        constrs: &[Constr],
+        names: &[&str],
+    ) -> crate::error::Result<()> {
+        let names = names.iter().map(|&name| CString::new(name)).collect::<Result<Vec<_>, _>>()?;
+        let name_ptrs: Vec<_> = names.iter().map(|name| name.as_ptr()).collect();
+        self.set_row_values(constrs, &name_ptrs, copt_sys::COPT_SetRowNames)?;
+        self.constr_names.get_mut().clear();
+        Ok(())
+    }
+
+    /// Get the coefficient of a variable in a linear constraint.
+    pub fn get_coeff(&self, constr: Constr, var: Var) -> crate::error::Result<f64> {
+        let (row, col) = (self.constr_index(constr)?, self.var_index(var)?);
+        let mut value = 0.0;
+        self.check_apicall(unsafe { copt_sys::COPT_GetElem(self.model, col, row, &mut value) })?;
+        Ok(value)
+    }
+
+    /// Set the coefficient of a variable in a linear constraint.
+    pub fn set_coeff(&mut self, constr: Constr, var: Var, value: f64) -> crate::error::Result<()> {
+        let (row, col) = (self.constr_index(constr)?, self.var_index(var)?);
+        self.check_apicall(unsafe { copt_sys::COPT_SetElem(self.model, col, row, value) })
+    }
+
+    /// Set many `(constr, var, value)` coefficients at once.
+    ///
+    /// All handles are checked before any coefficient is changed.
+    pub fn set_coeffs(&mut self, coeffs: &[(Constr, Var, f64)]) -> crate::error::Result<()> {
+        let elems = coeffs
+            .iter()
+            .map(|&(constr, var, value)| {
+                Ok((self.constr_index(constr)?, self.var_index(var)?, value))
+            })
+            .collect::<crate::error::Result<Vec<_>>>()?;
+        for (row, col, value) in elems {
+            self.check_apicall(unsafe { copt_sys::COPT_SetElem(self.model, col, row, value) })?;
+        }
+        Ok(())
     }
 
     pub fn set_objective_low_level(
         &mut self,
-        vars: &[i32],
+        vars: &[Var],
         coeffs: &[f64],
         sense: ModelSense,
     ) -> crate::error::Result<()> {
         if vars.len() != coeffs.len() {
             return Err(crate::error::Error::InconsitentDims);
         }
+        let vars = self.var_indices(vars)?;
         self.check_apicall(unsafe {
             copt_sys::COPT_SetColObj(self.model, vars.len() as i32, vars.as_ptr(), coeffs.as_ptr())
         })?;
@@ -254,6 +1666,7 @@ impl Model {
         sense: ModelSense,
     ) -> crate::error::Result<()> {
         let (vars, coeffs, offset) = expr.into();
+        let vars = self.var_indices(&vars)?;
         self.check_apicall(unsafe {
             copt_sys::COPT_SetColObj(self.model, vars.len() as i32, vars.as_ptr(), coeffs.as_ptr())
         })?;
@@ -264,6 +1677,57 @@ impl Model {
         Ok(())
     }
 
+    /// Set a quadratic objective, replacing any previous quadratic terms.
+    pub fn set_objective_quad(
+        &mut self,
+        expr: crate::expr::QuadExpr,
+        sense: ModelSense,
+    ) -> crate::error::Result<()> {
+        let (rows, cols, coeffs, lin) = expr.into();
+        let rows = self.var_indices(&rows)?;
+        let cols = self.var_indices(&cols)?;
+        self.set_objective(lin, sense)?;
+        self.check_apicall(unsafe { copt_sys::COPT_DelQuadObj(self.model) })?;
+        if coeffs.is_empty() {
+            return Ok(());
+        }
+        self.check_apicall(unsafe {
+            copt_sys::COPT_SetQuadObj(
+                self.model,
+                coeffs.len() as std::ffi::c_int,
+                rows.as_ptr(),
+                cols.as_ptr(),
+                coeffs.as_ptr(),
+            )
+        })
+    }
+
+    /// Get the quadratic terms of the objective.
+    pub fn get_objective_quad(&self) -> crate::error::Result<crate::expr::QuadExpr> {
+        let size = self.get_attribute(IntAttr::QElems)? as usize;
+        let mut num = 0;
+        let mut rows = vec![0; size];
+        let mut cols = vec![0; size];
+        let mut coeffs = vec![0.0; size];
+        self.check_apicall(unsafe {
+            copt_sys::COPT_GetQuadObj(
+                self.model,
+                &mut num,
+                rows.as_mut_ptr(),
+                cols.as_mut_ptr(),
+                coeffs.as_mut_ptr(),
+            )
+        })?;
+        let rows: Vec<_> = rows.iter().map(|&row| self.var_at(row)).collect();
+        let cols: Vec<_> = cols.iter().map(|&col| self.var_at(col)).collect();
+        Ok(crate::expr::QuadExpr::new().add_terms(&coeffs, &rows, &cols))
+    }
+
+    /// Remove the quadratic terms of the objective.
+    pub fn remove_objective_quad(&mut self) -> crate::error::Result<()> {
+        self.check_apicall(unsafe { copt_sys::COPT_DelQuadObj(self.model) })
+    }
+
     pub fn get_attribute<A: crate::attribute::Attr>(
         &self,
         attr: A,
@@ -296,14 +1760,14 @@ impl Model {
         Ok(())
     }
 
-    pub fn add_mip_start(&mut self,start:&Vec<(i32, f64)>)-> crate::error::Result<()> {
+    pub fn add_mip_start(&mut self,start:&[(Var, f64)])-> crate::error::Result<()> {
         if start.is_empty() {
             return Ok(());
         }
         let mut vars=Vec::with_capacity(start.len());
         let mut values = Vec::with_capacity(start.len());
         for (var,value) in start{
-            vars.push(*var);
+            vars.push(self.var_index(*var)?);
             values.push(*value);
         }
         self.check_apicall(unsafe { copt_sys::COPT_AddMipStart(self.model,start.len() as i32,vars.as_ptr(),values.as_ptr()) })
@@ -329,11 +1793,11 @@ impl Model {
     where G: FnMut() -> bool + 'static {
         let terminate_usrdata = TerminateCallbackData { callback: &mut terminate_callback };
         self.check_apicall(unsafe {
-            copt_sys::COPT_SetCallback(self.model, Some(terminate_callback_wrapper), 4i32/*COPT_CBCONTEXT_MIPNODE */,transmute(&terminate_usrdata))
+            copt_sys::COPT_SetCallback(self.model, Some(terminate_callback_wrapper), CallbackContext::MipNode.into(),transmute(&terminate_usrdata))
         })?;
         self.check_apicall(unsafe { copt_sys::COPT_Solve(self.model) })?;
         self.check_apicall(unsafe {
-            copt_sys::COPT_SetCallback(self.model, None, 4i32/*COPT_CBCONTEXT_MIPNODE */,null_mut())
+            copt_sys::COPT_SetCallback(self.model, None, CallbackContext::MipNode.into(),null_mut())
         })
     }
 
@@ -346,29 +1810,72 @@ impl Model {
             copt_sys::COPT_SetLogCallback(self.model, Some(callback_wrapper), transmute(&usrdata))
         })?;
         self.check_apicall(unsafe {
-            copt_sys::COPT_SetCallback(self.model, Some(terminate_callback_wrapper), 4i32/*COPT_CBCONTEXT_MIPNODE */,transmute(&terminate_usrdata))
+            copt_sys::COPT_SetCallback(self.model, Some(terminate_callback_wrapper), CallbackContext::MipNode.into(),transmute(&terminate_usrdata))
         })?;
         self.check_apicall(unsafe { copt_sys::COPT_Solve(self.model) })?;
         self.check_apicall(unsafe {
             copt_sys::COPT_SetLogCallback(self.model, None, null_mut())
         })?;
         self.check_apicall(unsafe {
-            copt_sys::COPT_SetCallback(self.model, None, 4i32/*COPT_CBCONTEXT_MIPNODE */,null_mut())
+            copt_sys::COPT_SetCallback(self.model, None, CallbackContext::MipNode.into(),null_mut())
         })
     }
 
+    /// Solve the model, invoking `callback` in each of the given contexts.
+    pub fn optimize_with_callback<C>(
+        &mut self,
+        contexts: &[CallbackContext],
+        mut callback: C,
+    ) -> crate::error::Result<()>
+    where
+        C: crate::callback::Callback,
+    {
+        let mask = contexts.iter().fold(0, |mask, &context| mask | context as std::ffi::c_int);
+        let mut usrdata = CallbackData { model: self, callback: &mut callback, error: None };
+        self.check_apicall(unsafe {
+            copt_sys::COPT_SetCallback(
+                self.model,
+                Some(general_callback_wrapper),
+                mask,
+                &mut usrdata as *mut CallbackData as *mut std::ffi::c_void,
+            )
+        })?;
+        let solved = self.check_apicall(unsafe { copt_sys::COPT_Solve(self.model) });
+        self.check_apicall(unsafe {
+            copt_sys::COPT_SetCallback(self.model, None, mask, null_mut())
+        })?;
+        solved?;
+        match usrdata.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
     pub fn get_results(&self) -> crate::error::Result<Vec<f64>> {
-        let mut res = vec![0.0; self.var_count as usize];
+        let mut res = vec![0.0; self.vars.len()];
         self.check_apicall(unsafe { copt_sys::COPT_GetSolution(self.model, res.as_mut_ptr()) })?;
         Ok(res)
     }
 
     pub fn get_lp_results(&self) -> crate::error::Result<Vec<f64>> {
-        let mut res = vec![0.0; self.var_count as usize];
+        let mut res = vec![0.0; self.vars.len()];
         self.check_apicall(unsafe { copt_sys::COPT_GetLpSolution(self.model, res.as_mut_ptr(), null_mut(),null_mut(),null_mut()) })?;
         Ok(res)
     }
 
+    /// Drop the solution, MIP starts and other solve results, keeping the problem.
+    pub fn reset_solution(&mut self) -> crate::error::Result<()> {
+        self.check_apicall(unsafe { copt_sys::COPT_Reset(self.model, 1) })
+    }
+
+    /// Remove everything from the problem, keeping the parameters.
+    ///
+    /// Handles created before the call refer to removed objects afterwards.
+    pub fn clear(&mut self) -> crate::error::Result<()> {
+        self.check_apicall(unsafe { copt_sys::COPT_ClearProb(self.model) })?;
+        self.sync_handles()
+    }
+
     pub fn terminate(&self) {
         unsafe { copt_sys::COPT_Interrupt(self.model) };
     }
@@ -377,13 +1884,62 @@ impl Model {
         let low_case = filename.to_ascii_lowercase();
         let filename_c = CString::new(filename)?;
         if low_case.ends_with(".mps") {
-            self.check_apicall(unsafe { copt_sys::COPT_ReadMps(self.model, filename_c.as_ptr()) })
+            self.check_apicall(unsafe { copt_sys::COPT_ReadMps(self.model, filename_c.as_ptr()) })?;
         } else if low_case.ends_with(".lp") {
-            self.check_apicall(unsafe { copt_sys::COPT_ReadLp(self.model, filename_c.as_ptr()) })
+            self.check_apicall(unsafe { copt_sys::COPT_ReadLp(self.model, filename_c.as_ptr()) })?;
         } else {
             use crate::env::ErrorFromAPI;
-            Err(self.env.error_from_api(2))
+            return Err(self.env.error_from_api(2));
+        }
+        self.sync_handles()
+    }
+
+    /// Get the name of a variable.
+    pub fn var_name(&self, var: Var) -> crate::error::Result<String> {
+        self.get_name(self.var_index(var)?, copt_sys::COPT_GetColName)
+    }
+
+    /// Get the name of a constraint.
+    pub fn constr_name(&self, constr: Constr) -> crate::error::Result<String> {
+        self.get_name(self.constr_index(constr)?, copt_sys::COPT_GetRowName)
+    }
+
+    /// Find a variable by name.
+    pub fn get_var_by_name(&self, name: &str) -> crate::error::Result<Option<Var>> {
+        let cached = self.var_names.borrow().get(name).copied();
+        if let Some(serial) = cached.filter(|&serial| self.vars.index(serial).is_some()) {
+            return Ok(Some(Var::new(self.id, serial)));
+        }
+        let index = self.get_index_by_name(name, copt_sys::COPT_GetColIdx)?;
+        let var = index.map(|index| self.var_at(index));
+        if let Some(var) = var {
+            self.var_names.borrow_mut().insert(name.to_owned(), var.serial);
+        }
+        Ok(var)
+    }
+
+    /// Find a constraint by name.
+    pub fn get_constr_by_name(&self, name: &str) -> crate::error::Result<Option<Constr>> {
+        let cached = self.constr_names.borrow().get(name).copied();
+        if let Some(serial) = cached.filter(|&serial| self.constrs.index(serial).is_some()) {
+            return Ok(Some(Constr::new(self.id, serial)));
+        }
+        let index = self.get_index_by_name(name, copt_sys::COPT_GetRowIdx)?;
+        let constr = index.map(|index| self.constr_at(index));
+        if let Some(constr) = constr {
+            self.constr_names.borrow_mut().insert(name.to_owned(), constr.serial);
         }
+        Ok(constr)
+    }
+
+    /// Iterate over the handles of all variables, in column order.
+    pub fn vars(&self) -> impl Iterator<Item = Var> + '_ {
+        (0..self.vars.len() as i32).map(move |index| self.var_at(index))
+    }
+
+    /// Iterate over the handles of all linear constraints, in row order.
+    pub fn constrs(&self) -> impl Iterator<Item = Constr> + '_ {
+        (0..self.constrs.len() as i32).map(move |index| self.constr_at(index))
     }
 
     pub fn write(&self, filename: &str) -> crate::error::Result<()> {
@@ -421,6 +1977,336 @@ impl Model {
     //     })
     // }
 
+    /// Get the current column index of a variable, e.g. to look it up in `get_results`.
+    pub fn var_index(&self, var: Var) -> crate::error::Result<i32> {
+        self.index_in(&self.vars, var)
+    }
+
+    /// Get the current row index of a constraint.
+    pub fn constr_index(&self, constr: Constr) -> crate::error::Result<i32> {
+        self.index_in(&self.constrs, constr)
+    }
+
+    /// Translate a handle into its current COPT index.
+    fn index_in<H: Handle>(&self, map: &HandleMap, handle: H) -> crate::error::Result<i32> {
+        if handle.model_id() != self.id {
+            return Err(crate::error::Error::ModelMismatch);
+        }
+        map.index(handle.serial()).ok_or(crate::error::Error::RemovedHandle)
+    }
+
+    fn indices_in<H: Handle>(
+        &self,
+        map: &HandleMap,
+        handles: &[H],
+    ) -> crate::error::Result<Vec<i32>> {
+        handles.iter().map(|&handle| self.index_in(map, handle)).collect()
+    }
+
+    fn var_indices(&self, vars: &[Var]) -> crate::error::Result<Vec<i32>> {
+        self.indices_in(&self.vars, vars)
+    }
+
+    fn constr_indices(&self, constrs: &[Constr]) -> crate::error::Result<Vec<i32>> {
+        self.indices_in(&self.constrs, constrs)
+    }
+
+    fn new_var(&mut self) -> Var {
+        Var::new(self.id, self.vars.push())
+    }
+
+    fn new_constr(&mut self) -> Constr {
+        Constr::new(self.id, self.constrs.push())
+    }
+
+    /// Pass one value per variable to a `COPT_SetCol*` function.
+    fn set_col_values<T>(
+        &mut self,
+        vars: &[Var],
+        values: &[T],
+        setter: unsafe extern "C" fn(
+            *mut copt_sys::copt_prob,
+            std::ffi::c_int,
+            *const std::ffi::c_int,
+            *const T,
+        ) -> std::ffi::c_int,
+    ) -> crate::error::Result<()> {
+        if vars.len() != values.len() {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        let list = self.var_indices(vars)?;
+        self.check_apicall(unsafe {
+            setter(self.model, list.len() as std::ffi::c_int, list.as_ptr(), values.as_ptr())
+        })
+    }
+
+    /// Pass one value per constraint to a `COPT_SetRow*` function.
+    fn set_row_values<T>(
+        &mut self,
+        constrs: &[Constr],
+        values: &[T],
+        setter: unsafe extern "C" fn(
+            *mut copt_sys::copt_prob,
+            std::ffi::c_int,
+            *const std::ffi::c_int,
+            *const T,
+        ) -> std::ffi::c_int,
+    ) -> crate::error::Result<()> {
+        if constrs.len() != values.len() {
+            return Err(crate::error::Error::InconsitentDims);
+        }
+        let list = self.constr_indices(constrs)?;
+        self.check_apicall(unsafe {
+            setter(self.model, list.len() as std::ffi::c_int, list.as_ptr(), values.as_ptr())
+        })
+    }
+
+    /// Query a value for each of the given indices through a `COPT_Get*Info` function.
+    fn get_info(
+        &self,
+        getter: unsafe extern "C" fn(
+            *mut copt_sys::copt_prob,
+            *const std::ffi::c_char,
+            std::ffi::c_int,
+            *const std::ffi::c_int,
+            *mut std::ffi::c_double,
+        ) -> std::ffi::c_int,
+        name: &str,
+        list: &[i32],
+    ) -> crate::error::Result<Vec<f64>> {
+        let name_c = CString::new(name)?;
+        let mut info = vec![0.0; list.len()];
+        self.check_apicall(unsafe {
+            getter(
+                self.model,
+                name_c.as_ptr(),
+                list.len() as std::ffi::c_int,
+                list.as_ptr(),
+                info.as_mut_ptr(),
+            )
+        })?;
+        Ok(info)
+    }
+
+    /// Replace all handles after COPT loaded a new problem on its own, e.g. from a file.
+    fn sync_handles(&mut self) -> crate::error::Result<()> {
+        let cols = self.get_attribute(IntAttr::Cols)?;
+        let rows = self.get_attribute(IntAttr::Rows)?;
+        let qconstrs = self.get_attribute(IntAttr::QConstrs)?;
+        let soss = self.get_attribute(IntAttr::Soss)?;
+        let indicators = self.get_attribute(IntAttr::Indicators)?;
+        let cones = self.get_attribute(IntAttr::Cones)?;
+        let exp_cones = self.get_attribute(IntAttr::ExpCones)?;
+        let psd_vars = self.get_attribute(IntAttr::PSDCols)?;
+        let sym_mats = self.get_attribute(IntAttr::SymMats)?;
+        let psd_constrs = self.get_attribute(IntAttr::PSDConstrs)?;
+        let lmis = self.get_attribute(IntAttr::LMIConstrs)?;
+        self.vars.reset(cols as usize);
+        self.constrs.reset(rows as usize);
+        self.qconstrs.reset(qconstrs as usize);
+        self.soss.reset(soss as usize);
+        self.indicators.reset(indicators as usize);
+        self.cones.reset(cones as usize);
+        self.cone_vars.clear();
+        self.exp_cones.reset(exp_cones as usize);
+        self.exp_cone_vars.clear();
+        self.psd_vars.reset(psd_vars as usize);
+        self.sym_mats.reset(sym_mats as usize);
+        self.psd_constrs.reset(psd_constrs as usize);
+        self.lmis.reset(lmis as usize);
+        self.var_names.get_mut().clear();
+        self.constr_names.get_mut().clear();
+        Ok(())
+    }
+
+    /// Fetch all LMI slacks and duals through `COPT_GetLMISolution`, along with the range
+    /// belonging to `lmi`.
+    fn get_lmi_solution(
+        &self,
+        lmi: Lmi,
+    ) -> crate::error::Result<(Vec<f64>, Vec<f64>, std::ops::Range<usize>)> {
+        let index = self.index_in(&self.lmis, lmi)? as usize;
+        let mut offsets = vec![0];
+        for (i, dim) in self.get_lmi_dims()?.into_iter().enumerate() {
+            let dim = dim as usize;
+            offsets.push(offsets[i] + dim * (dim + 1) / 2);
+        }
+        let mut slacks = vec![0.0; offsets[offsets.len() - 1]];
+        let mut duals = vec![0.0; slacks.len()];
+        self.check_apicall(unsafe {
+            copt_sys::COPT_GetLMISolution(self.model, slacks.as_mut_ptr(), duals.as_mut_ptr())
+        })?;
+        Ok((slacks, duals, offsets[index]..offsets[index + 1]))
+    }
+
+    /// Query the dimension of every LMI of the model through `COPT_GetLMIConstr`.
+    fn get_lmi_dims(&self) -> crate::error::Result<Vec<i32>> {
+        let list: Vec<i32> = (0..self.lmis.len() as i32).collect();
+        let mut dims = vec![0; list.len()];
+        let mut size = 0;
+        self.check_apicall(unsafe {
+            copt_sys::COPT_GetLMIConstr(
+                self.model,
+                list.len() as std::ffi::c_int,
+                list.as_ptr(),
+                null_mut(),
+                dims.as_mut_ptr(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                0,
+                &mut size,
+            )
+        })?;
+        Ok(dims)
+    }
+
+    /// Fetch the whole PSD part of the solution through `COPT_GetPSDSolution`.
+    fn get_psd_solution(&self) -> crate::error::Result<PsdSolution> {
+        let list: Vec<i32> = (0..self.psd_vars.len() as i32).collect();
+        let mut dims = vec![0; list.len()];
+        let mut lens = vec![0; list.len()];
+        self.check_apicall(unsafe {
+            copt_sys::COPT_GetPSDCols(
+                self.model,
+                list.len() as std::ffi::c_int,
+                list.as_ptr(),
+                dims.as_mut_ptr(),
+                lens.as_mut_ptr(),
+            )
+        })?;
+        let mut offsets = vec![0];
+        for len in lens {
+            offsets.push(offsets[offsets.len() - 1] + len as usize);
+        }
+        let mut values = vec![0.0; offsets[offsets.len() - 1]];
+        let mut col_duals = vec![0.0; values.len()];
+        let mut row_slacks = vec![0.0; self.psd_constrs.len()];
+        let mut row_duals = vec![0.0; row_slacks.len()];
+        self.check_apicall(unsafe {
+            copt_sys::COPT_GetPSDSolution(
+                self.model,
+                values.as_mut_ptr(),
+                row_slacks.as_mut_ptr(),
+                row_duals.as_mut_ptr(),
+                col_duals.as_mut_ptr(),
+            )
+        })?;
+        Ok(PsdSolution { offsets, values, col_duals, row_slacks, row_duals })
+    }
+
+    /// Query a row or column name through `COPT_GetRowName` or `COPT_GetColName`.
+    fn get_name(
+        &self,
+        index: i32,
+        getter: unsafe extern "C" fn(
+            *mut copt_sys::copt_prob,
+            std::ffi::c_int,
+            *mut std::ffi::c_char,
+            std::ffi::c_int,
+            *mut std::ffi::c_int,
+        ) -> std::ffi::c_int,
+    ) -> crate::error::Result<String> {
+        let mut size = 0;
+        self.check_apicall(unsafe { getter(self.model, index, null_mut(), 0, &mut size) })?;
+        let mut buff: Vec<std::ffi::c_char> = vec![0; size as usize + 1];
+        self.check_apicall(unsafe {
+            getter(self.model, index, buff.as_mut_ptr(), buff.len() as std::ffi::c_int, null_mut())
+        })?;
+        Ok(util::Into::into(buff))
+    }
+
+    /// Look up a row or column index through `COPT_GetRowIdx` or `COPT_GetColIdx`.
+    fn get_index_by_name(
+        &self,
+        name: &str,
+        getter: unsafe extern "C" fn(
+            *mut copt_sys::copt_prob,
+            *const std::ffi::c_char,
+            *mut std::ffi::c_int,
+        ) -> std::ffi::c_int,
+    ) -> crate::error::Result<Option<i32>> {
+        let name_c = CString::new(name)?;
+        let mut index = -1;
+        self.check_apicall(unsafe { getter(self.model, name_c.as_ptr(), &mut index) })?;
+        Ok(if index >= 0 { Some(index) } else { None })
+    }
+
+    fn var_at(&self, index: i32) -> Var {
+        Var::new(self.id, self.vars.serial(index))
+    }
+
+    fn constr_at(&self, index: i32) -> Constr {
+        Constr::new(self.id, self.constrs.serial(index))
+    }
+
+    /// Fetch rows or columns in CSR/CSC form through `COPT_GetRows` or `COPT_GetCols`.
+    ///
+    /// The first call only asks for the number of nonzeros, the second one fills the buffers.
+    fn get_matrix(
+        &self,
+        list: &[i32],
+        getter: unsafe extern "C" fn(
+            *mut copt_sys::copt_prob,
+            std::ffi::c_int,
+            *const std::ffi::c_int,
+            *mut std::ffi::c_int,
+            *mut std::ffi::c_int,
+            *mut std::ffi::c_int,
+            *mut std::ffi::c_double,
+            std::ffi::c_int,
+            *mut std::ffi::c_int,
+        ) -> std::ffi::c_int,
+    ) -> crate::error::Result<SparseMatrix> {
+        let num = list.len() as std::ffi::c_int;
+        let mut beg = vec![0; list.len()];
+        let mut cnt = vec![0; list.len()];
+        let mut size = 0;
+        self.check_apicall(unsafe {
+            getter(
+                self.model,
+                num,
+                list.as_ptr(),
+                beg.as_mut_ptr(),
+                cnt.as_mut_ptr(),
+                null_mut(),
+                null_mut(),
+                0,
+                &mut size,
+            )
+        })?;
+        let mut ind = vec![0; size as usize];
+        let mut val = vec![0.0; size as usize];
+        self.check_apicall(unsafe {
+            getter(
+                self.model,
+                num,
+                list.as_ptr(),
+                beg.as_mut_ptr(),
+                cnt.as_mut_ptr(),
+                ind.as_mut_ptr(),
+                val.as_mut_ptr(),
+                size,
+                null_mut(),
+            )
+        })?;
+        Ok(SparseMatrix { beg, cnt, ind, val })
+    }
+
+    /// Sorted, deduplicated indices of the given handles.
+    fn removal_list<H: Handle>(
+        &self,
+        map: &HandleMap,
+        handles: &[H],
+    ) -> crate::error::Result<Vec<i32>> {
+        let mut list = self.indices_in(map, handles)?;
+        list.sort_unstable();
+        list.dedup();
+        Ok(list)
+    }
+
     fn check_apicall(&self, error: std::ffi::c_int) -> crate::error::Result<()> {
         if error != 0 {
             use crate::env::ErrorFromAPI;
@@ -436,3 +2322,75 @@ impl Drop for Model {
         self.model = null_mut();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `min x0 + 2 x1` subject to `x0 + x1 >= 1` and `x1 <= 3`.
+    fn csc_problem() -> CscProblem {
+        CscProblem {
+            sense: ModelSense::Minimize,
+            obj_const: 0.0,
+            obj: vec![1.0, 2.0],
+            col_ptr: vec![0, 1, 3],
+            row_ind: vec![0, 0, 1],
+            values: vec![1.0, 1.0, 1.0],
+            vtypes: vec![],
+            lbs: vec![],
+            ubs: vec![],
+            senses: vec![ConstrSense::Greater, ConstrSense::Less],
+            rhs: vec![1.0, 3.0],
+            col_names: vec![],
+            row_names: vec![],
+        }
+    }
+
+    fn assert_inconsistent(prob: CscProblem) {
+        assert!(matches!(prob.check_dims(), Err(crate::error::Error::InconsitentDims)));
+    }
+
+    #[test]
+    fn check_dims_accepts_consistent_problem() {
+        assert!(csc_problem().check_dims().is_ok());
+        let prob = CscProblem {
+            vtypes: vec![VarType::Continuous, VarType::Integer],
+            lbs: vec![0.0, 0.0],
+            ubs: vec![1.0, 1.0],
+            col_names: vec!["x0".into(), "x1".into()],
+            row_names: vec!["r0".into(), "r1".into()],
+            ..csc_problem()
+        };
+        assert!(prob.check_dims().is_ok());
+    }
+
+    #[test]
+    fn check_dims_rejects_short_col_ptr() {
+        assert_inconsistent(CscProblem { col_ptr: vec![0, 1], ..csc_problem() });
+        assert_inconsistent(CscProblem { col_ptr: vec![], ..csc_problem() });
+    }
+
+    #[test]
+    fn check_dims_rejects_bad_col_ptr_values() {
+        assert_inconsistent(CscProblem { col_ptr: vec![0, 3, 2], ..csc_problem() });
+        assert_inconsistent(CscProblem { col_ptr: vec![1, 1, 3], ..csc_problem() });
+        assert_inconsistent(CscProblem { col_ptr: vec![0, 1, 2], ..csc_problem() });
+    }
+
+    #[test]
+    fn check_dims_rejects_out_of_range_rows() {
+        assert_inconsistent(CscProblem { row_ind: vec![0, 0, 2], ..csc_problem() });
+        assert_inconsistent(CscProblem { row_ind: vec![0, -1, 1], ..csc_problem() });
+        assert_inconsistent(CscProblem { values: vec![1.0, 1.0], ..csc_problem() });
+    }
+
+    #[test]
+    fn check_dims_rejects_wrong_optional_lengths() {
+        assert_inconsistent(CscProblem { vtypes: vec![VarType::Binary], ..csc_problem() });
+        assert_inconsistent(CscProblem { lbs: vec![0.0; 3], ..csc_problem() });
+        assert_inconsistent(CscProblem { ubs: vec![1.0], ..csc_problem() });
+        assert_inconsistent(CscProblem { col_names: vec!["x0".into()], ..csc_problem() });
+        assert_inconsistent(CscProblem { row_names: vec!["r0".into()], ..csc_problem() });
+        assert_inconsistent(CscProblem { rhs: vec![1.0], ..csc_problem() });
+    }
+}